@@ -1,36 +1,158 @@
-use dotenv::var;
-use reqwest::blocking::Client;
-use serde_json::Value;
+pub mod cache;
+pub mod endpoints;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+pub mod paging;
+pub mod stats;
+pub mod tokens;
 
-use crate::util::BAD_JSON_ERR_MSG;
+use reqwest::{blocking::Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::{from_slice, from_value, Value};
+use std::{error::Error, time::Instant};
+use tracing::{info, warn};
 
-/// The URL for getting player info from the Clash Royale API.
-/// Appending the player's ID to the end will give the URL to get their data.
-pub const API_PLAYER_URL: &str = "https://api.clashroyale.com/v1/players/%23";
+use crate::{logging::log_url, util::BAD_JSON_ERR_MSG};
+
+use stats::{record_call, record_error, BAD_JSON_REASON, NO_RESPONSE_REASON};
+use tokens::{is_rejected, pool, redact};
+
+/// Message for getting no response from the Clash Royale API.
+const NO_RESPONSE_ERR_MSG: &str = "Got no API response";
 
 /// Makes a JSON request to the given URL and returns the response or an error
 /// if the call or deserialization fails.
+/// A request rejected with one developer token is retried with the others.
+///
+/// This blocks the current thread; see `nonblocking::api_call` (behind the
+/// `async` feature) for use inside an async runtime.
 pub fn api_call(url: &str) -> Result<Value, String> {
-    let token = get_dev_token()?;
-    let json: Value = Client::new()
-        .get(url)
-        .bearer_auth(token)
-        .send()
-        .map_err(|_| "Got no API response".to_string())?
-        .json()
-        .map_err(|_| BAD_JSON_ERR_MSG)?;
+    let pool = pool()?;
+    let mut result = Err(NO_RESPONSE_ERR_MSG.to_string());
+
+    for (retry, (index, token)) in pool.order().enumerate() {
+        record_call();
+        let started = Instant::now();
+        let response = Client::new()
+            .get(url)
+            .bearer_auth(token)
+            .send()
+            .map_err(|e| no_response(url, retry, e.without_url()))?;
+        let status = response.status();
+        let body = response
+            .bytes()
+            .map_err(|e| no_response(url, retry, e.without_url()))?;
+        log_response(url, index, retry, status, body.len(), started);
 
+        result = parse_body(&body).and_then(check_response);
+        if !should_retry(url, index, retry, status, &result) {
+            break;
+        }
+        pool.reject(index);
+    }
+
+    result
+}
+
+/// Makes a JSON request to the given URL and returns the response as the given
+/// model, or an error if the call or deserialization fails.
+pub fn api_get<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+    api_call(url).and_then(parse_model)
+}
+
+/// Returns the response back, or an error if the Clash Royale API responded
+/// with one.
+fn check_response(json: Value) -> Result<Value, String> {
     // Catch error responses.
     // All have the `reason` value, good responses don't.
     if let Some(err) = json.get("reason").and_then(|v| v.as_str()) {
+        record_error(err);
         Err(format!("Clash Royale API returned an error \"{err}\""))
     } else {
         Ok(json)
     }
 }
 
-/// Retrieves and returns the developer token from `.env`, or an error if it
-/// isn't found.
-fn get_dev_token() -> Result<String, String> {
-    var("TOKEN").map_err(|_| "No developer token found in `.env`".to_string())
+/// Logs a request's response.
+fn log_response(
+    url: &str,
+    token: usize,
+    retry: usize,
+    status: StatusCode,
+    bytes: usize,
+    started: Instant,
+) {
+    info!(
+        url = %log_url(url),
+        status = status.as_u16(),
+        latency_ms = started.elapsed().as_millis() as u64,
+        bytes,
+        retry,
+        token = token + 1,
+        "API request"
+    );
+}
+
+/// Logs a failed request and returns whether it should be retried with another
+/// developer token, i.e. the token was rejected.
+fn should_retry(
+    url: &str,
+    token: usize,
+    retry: usize,
+    status: StatusCode,
+    result: &Result<Value, String>,
+) -> bool {
+    let Err(e) = result else {
+        return false;
+    };
+
+    let rejected = is_rejected(status);
+    warn!(
+        url = %log_url(url),
+        retry,
+        token = token + 1,
+        error = %e,
+        "{}",
+        if rejected {
+            "API request rejected with developer token"
+        } else {
+            "API request failed"
+        }
+    );
+
+    rejected
+}
+
+/// Records and logs a request that got no response, and returns the error.
+fn no_response(url: &str, retry: usize, error: impl Error) -> &'static str {
+    // Include the causes, e.g. the connection being refused.
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+
+    record_error(NO_RESPONSE_REASON);
+    warn!(
+        url = %log_url(url),
+        retry,
+        error = %redact(&message),
+        "API request got no response"
+    );
+
+    NO_RESPONSE_ERR_MSG
+}
+
+/// Deserializes the response body as JSON.
+fn parse_body(body: &[u8]) -> Result<Value, String> {
+    from_slice(body).map_err(|_| {
+        record_error(BAD_JSON_REASON);
+        BAD_JSON_ERR_MSG.to_string()
+    })
+}
+
+/// Deserializes the JSON response into the given model.
+fn parse_model<T: DeserializeOwned>(json: Value) -> Result<T, String> {
+    from_value(json).map_err(|_| BAD_JSON_ERR_MSG.to_string())
 }
@@ -0,0 +1,69 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tracing::info;
+
+use crate::logging::log_url;
+
+use super::{api_call, parse_model, stats::record_cache_hit};
+
+/// An in-memory cache of API responses by URL, so repeated requests within
+/// its time to live don't count against the rate limit.
+pub struct ResponseCache {
+    /// How long responses are kept.
+    ttl: Duration,
+
+    /// The cached responses and when they were fetched.
+    entries: Mutex<HashMap<String, (Instant, Value)>>,
+}
+
+impl ResponseCache {
+    /// Returns an empty cache that keeps responses for the given time.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the cached response from the URL, or makes the request if it
+    /// isn't cached or has expired.
+    /// Error responses aren't cached.
+    pub fn call(&self, url: &str) -> Result<Value, String> {
+        if let Some((fetched, json)) = self.lock().get(url) {
+            if fetched.elapsed() < self.ttl {
+                record_cache_hit();
+                info!(url = %log_url(url), cache = "hit", "API response from cache");
+                return Ok(json.clone());
+            }
+        }
+
+        info!(url = %log_url(url), cache = "miss", "API response not cached");
+
+        let json = api_call(url)?;
+        let mut entries = self.lock();
+        entries.retain(|_, (fetched, _)| fetched.elapsed() < self.ttl);
+        entries.insert(url.to_string(), (Instant::now(), json.clone()));
+
+        Ok(json)
+    }
+
+    /// Returns the cached response from the URL as the given model, making the
+    /// request if needed.
+    pub fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+        self.call(url).and_then(parse_model)
+    }
+
+    /// Locks the entries, recovering them if another thread panicked while
+    /// holding the lock.
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (Instant, Value)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,218 @@
+//! URLs of the Clash Royale API's endpoints, shared by the blocking and async
+//! clients.
+
+use reqwest::Url;
+
+use crate::config;
+
+/// The base URL of the Clash Royale API.
+pub const API_BASE_URL: &str = "https://api.clashroyale.com/v1";
+
+/// Returns the API's base URL: the config's override (e.g. a proxy) if it has
+/// one, or `API_BASE_URL`.
+pub fn base_url() -> &'static str {
+    config::profile()
+        .base_url
+        .as_deref()
+        .unwrap_or(API_BASE_URL)
+        .trim_end_matches('/')
+}
+
+/// The query parameters of a list endpoint that choose which page to get.
+#[derive(Debug, Clone, Default)]
+pub struct PageQuery {
+    /// The maximum number of items on the page.
+    pub limit: Option<u32>,
+
+    /// Get the page after this cursor.
+    pub after: Option<String>,
+
+    /// Get the page before this cursor.
+    pub before: Option<String>,
+}
+
+impl PageQuery {
+    /// Returns the query's parameters, leaving out any that aren't set.
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(limit) = self.limit {
+            params.push(("limit", limit.to_string()));
+        }
+        if let Some(after) = &self.after {
+            params.push(("after", after.clone()));
+        }
+        if let Some(before) = &self.before {
+            params.push(("before", before.clone()));
+        }
+
+        params
+    }
+}
+
+/// The filters of a clan search.
+/// At least one must be set for the API to accept the search.
+#[derive(Debug, Clone, Default)]
+pub struct ClanSearchQuery {
+    /// Search for clans whose names contain this; at least 3 characters long.
+    pub name: Option<String>,
+
+    /// Only include clans from the location with this ID.
+    pub location_id: Option<i64>,
+
+    /// Only include clans with at least this many members.
+    pub min_members: Option<u32>,
+
+    /// Only include clans with at most this many members.
+    pub max_members: Option<u32>,
+
+    /// Only include clans with at least this score.
+    pub min_score: Option<u32>,
+}
+
+impl ClanSearchQuery {
+    /// Returns the query's parameters, leaving out any that aren't set.
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(name) = &self.name {
+            params.push(("name", name.clone()));
+        }
+        if let Some(location_id) = self.location_id {
+            params.push(("locationId", location_id.to_string()));
+        }
+        if let Some(min_members) = self.min_members {
+            params.push(("minMembers", min_members.to_string()));
+        }
+        if let Some(max_members) = self.max_members {
+            params.push(("maxMembers", max_members.to_string()));
+        }
+        if let Some(min_score) = self.min_score {
+            params.push(("minScore", min_score.to_string()));
+        }
+
+        params
+    }
+}
+
+/// Returns the given tag without its leading `#`, URL-encoded as `%23`.
+/// Tags may be given with or without the `#`.
+pub fn encode_tag(tag: &str) -> String {
+    format!("%23{}", tag.trim_start_matches('#'))
+}
+
+/// Returns the URL with the given query parameters appended, URL-encoded.
+/// Returns an error if the URL is invalid.
+pub fn with_query(url: &str, params: &[(&str, String)]) -> Result<String, String> {
+    if params.is_empty() {
+        return Ok(url.to_string());
+    }
+
+    Url::parse_with_params(url, params)
+        .map(String::from)
+        .map_err(|_| format!("Invalid API URL `{url}`"))
+}
+
+/// Returns the URL to get the data of the player with the given tag.
+pub fn player(tag: &str) -> String {
+    format!("{}/players/{}", base_url(), encode_tag(tag))
+}
+
+/// Returns the URL to get the upcoming chests of the player with the given tag.
+pub fn upcoming_chests(tag: &str) -> String {
+    format!("{}/upcomingchests", player(tag))
+}
+
+/// Returns the URL to get the recent battles of the player with the given tag.
+pub fn battle_log(tag: &str) -> String {
+    format!("{}/battlelog", player(tag))
+}
+
+/// Returns the URL to get the data of all cards.
+pub fn cards() -> String {
+    format!("{}/cards", base_url())
+}
+
+/// Returns the URL to search for clans with the given filters, or an error if
+/// none are set.
+pub fn clan_search(query: &ClanSearchQuery) -> Result<String, String> {
+    let params = query.params();
+    if params.is_empty() {
+        return Err("Expected at least one clan search filter".to_string());
+    }
+
+    with_query(&format!("{}/clans", base_url()), &params)
+}
+
+/// Returns the URL to get the members of the clan with the given tag.
+pub fn clan_members(tag: &str) -> String {
+    format!("{}/clans/{}/members", base_url(), encode_tag(tag))
+}
+
+/// Returns the URL to get the current river race of the clan with the given
+/// tag.
+pub fn current_river_race(tag: &str) -> String {
+    format!("{}/clans/{}/currentriverrace", base_url(), encode_tag(tag))
+}
+
+/// Returns the URL to get the log of finished river races of the clan with the
+/// given tag.
+pub fn river_race_log(tag: &str) -> String {
+    format!("{}/clans/{}/riverracelog", base_url(), encode_tag(tag))
+}
+
+/// Returns the URL to search for tournaments with the given name, or an error
+/// if the name is invalid.
+pub fn tournament_search(name: &str) -> Result<String, String> {
+    with_query(
+        &format!("{}/tournaments", base_url()),
+        &[("name", name.to_string())],
+    )
+}
+
+/// Returns the URL to get the tournament with the given tag.
+pub fn tournament(tag: &str) -> String {
+    format!("{}/tournaments/{}", base_url(), encode_tag(tag))
+}
+
+/// Returns the URL to get the current and upcoming global tournaments.
+pub fn global_tournaments() -> String {
+    format!("{}/globaltournaments", base_url())
+}
+
+/// Returns the URL to get the current and upcoming challenges.
+pub fn challenges() -> String {
+    format!("{}/challenges", base_url())
+}
+
+/// Returns the URL to get all locations.
+pub fn locations() -> String {
+    format!("{}/locations", base_url())
+}
+
+/// Returns the URL to get the player trophy rankings of the location with the
+/// given ID.
+pub fn player_rankings(location_id: &str) -> String {
+    format!("{}/locations/{location_id}/rankings/players", base_url())
+}
+
+/// Returns the URL to get the clan rankings of the location with the given ID.
+pub fn clan_rankings(location_id: &str) -> String {
+    format!("{}/locations/{location_id}/rankings/clans", base_url())
+}
+
+/// Returns the URL to get the current Path of Legends rankings of the location
+/// with the given ID.
+pub fn path_of_legend_rankings(location_id: &str) -> String {
+    format!(
+        "{}/locations/{location_id}/pathoflegend/players",
+        base_url()
+    )
+}
+
+/// Returns the URL to get the global Path of Legends rankings of the season
+/// with the given ID (e.g. `2025-10`).
+pub fn path_of_legend_season_rankings(season_id: &str) -> String {
+    format!(
+        "{}/locations/global/pathoflegend/{season_id}/rankings/players",
+        base_url()
+    )
+}
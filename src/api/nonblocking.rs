@@ -0,0 +1,52 @@
+//! An async version of the API client for use inside an async runtime (e.g.
+//! `tokio`). Exposed by the `async` feature, which only makes this module
+//! public; no runtime is included, so the caller must provide one.
+
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::time::Instant;
+
+use super::{
+    check_response, log_response, no_response, parse_body, parse_model, should_retry,
+    stats::record_call, tokens::pool, NO_RESPONSE_ERR_MSG,
+};
+
+/// Makes a JSON request to the given URL and returns the response or an error
+/// if the call or deserialization fails.
+/// A request rejected with one developer token is retried with the others.
+pub async fn api_call(url: &str) -> Result<Value, String> {
+    let pool = pool()?;
+    let mut result = Err(NO_RESPONSE_ERR_MSG.to_string());
+
+    for (retry, (index, token)) in pool.order().enumerate() {
+        record_call();
+        let started = Instant::now();
+        let response = Client::new()
+            .get(url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| no_response(url, retry, e.without_url()))?;
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| no_response(url, retry, e.without_url()))?;
+        log_response(url, index, retry, status, body.len(), started);
+
+        result = parse_body(&body).and_then(check_response);
+        if !should_retry(url, index, retry, status, &result) {
+            break;
+        }
+        pool.reject(index);
+    }
+
+    result
+}
+
+/// Makes a JSON request to the given URL and returns the response as the given
+/// model, or an error if the call or deserialization fails.
+pub async fn api_get<T: DeserializeOwned>(url: &str) -> Result<T, String> {
+    api_call(url).await.and_then(parse_model)
+}
@@ -0,0 +1,123 @@
+//! Lazy pagination over the Clash Royale API's list endpoints.
+
+use std::vec::IntoIter;
+
+use serde::de::DeserializeOwned;
+
+use crate::models::paging::Page;
+
+use super::{
+    api_get,
+    endpoints::{with_query, PageQuery},
+};
+
+/// The number of items requested per page by default.
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// An iterator over the items of a list endpoint.
+/// Pages are requested as needed by following each page's `after` cursor.
+///
+/// If a request fails its error is yielded and iteration ends.
+pub struct Paginated<T> {
+    /// The list endpoint's URL, without page query parameters.
+    url: String,
+
+    /// The maximum number of items to yield, or `None` for all of them.
+    limit: Option<usize>,
+
+    /// The number of items requested per page.
+    page_size: u32,
+
+    /// The cursor of the next page to request, or `None` if there isn't one.
+    after: Option<String>,
+
+    /// Whether the first page has been requested yet.
+    started: bool,
+
+    /// The remaining items of the current page.
+    items: IntoIter<T>,
+
+    /// The number of items yielded so far.
+    yielded: usize,
+}
+
+/// Returns an iterator over the items of the list endpoint at the given URL.
+/// The query's `limit` is the total number of items to yield, and iteration
+/// starts after its `after` cursor if it's set.
+pub fn paginate<T: DeserializeOwned>(url: &str, query: &PageQuery) -> Paginated<T> {
+    Paginated {
+        url: url.to_string(),
+        limit: query.limit.map(|limit| limit as usize),
+        page_size: DEFAULT_PAGE_SIZE,
+        after: query.after.clone(),
+        started: false,
+        items: Vec::new().into_iter(),
+        yielded: 0,
+    }
+}
+
+impl<T: DeserializeOwned> Paginated<T> {
+    /// Sets the number of items requested per page.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Returns the cursor of the page after the last one requested, or `None`
+    /// if there isn't one.
+    /// Any items of the last page not yet yielded come before it.
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.after.as_deref()
+    }
+
+    /// Requests the next page and returns its items, or an error if the
+    /// request fails.
+    fn next_page(&mut self) -> Result<IntoIter<T>, String> {
+        // Don't request more than is needed to reach the limit.
+        let remaining = self.limit.map_or(usize::MAX, |limit| limit - self.yielded);
+        let query = PageQuery {
+            limit: Some(self.page_size.min(remaining.try_into().unwrap_or(u32::MAX))),
+            after: self.after.take(),
+            before: None,
+        };
+        let page: Page<T> = api_get(&with_query(&self.url, &query.params())?)?;
+
+        // An empty page has nothing after it, even if it has a cursor.
+        if !page.items.is_empty() {
+            self.after = page.paging.cursors.after;
+        }
+
+        Ok(page.items.into_iter())
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for Paginated<T> {
+    type Item = Result<T, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.limit.is_some_and(|limit| self.yielded >= limit) {
+            return None;
+        }
+
+        loop {
+            if let Some(item) = self.items.next() {
+                self.yielded += 1;
+                return Some(Ok(item));
+            }
+
+            // The current page is done; stop if there's no next one.
+            if self.started && self.after.is_none() {
+                return None;
+            }
+            self.started = true;
+
+            match self.next_page() {
+                Ok(items) => self.items = items,
+                Err(e) => {
+                    self.after = None;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
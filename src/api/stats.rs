@@ -0,0 +1,62 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+/// The number of requests made to the API.
+static CALLS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of requests answered by a `ResponseCache` instead of the API.
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of failed requests, by the API's reason (e.g. `notFound`), or
+/// `noResponse` or `badJson` if it didn't give one.
+static ERRORS: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+/// The reasons recorded for requests that failed without an API error.
+pub const NO_RESPONSE_REASON: &str = "noResponse";
+pub const BAD_JSON_REASON: &str = "badJson";
+
+/// Counts of the API requests made since the program started.
+pub struct ApiStats {
+    /// The number of requests made to the API.
+    pub calls: u64,
+
+    /// The number of requests answered by a `ResponseCache` instead.
+    pub cache_hits: u64,
+
+    /// The number of failed requests by reason.
+    pub errors: BTreeMap<String, u64>,
+}
+
+/// Returns the counts of the API requests made so far.
+pub fn api_stats() -> ApiStats {
+    ApiStats {
+        calls: CALLS.load(Ordering::Relaxed),
+        cache_hits: CACHE_HITS.load(Ordering::Relaxed),
+        errors: ERRORS
+            .lock()
+            .map(|errors| errors.clone())
+            .unwrap_or_default(),
+    }
+}
+
+/// Counts a request made to the API.
+pub(super) fn record_call() {
+    CALLS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts a request answered by a cache.
+pub(super) fn record_cache_hit() {
+    CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counts a failed request with the given reason.
+pub(super) fn record_error(reason: &str) {
+    if let Ok(mut errors) = ERRORS.lock() {
+        *errors.entry(reason.to_string()).or_default() += 1;
+    }
+}
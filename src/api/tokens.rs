@@ -0,0 +1,207 @@
+use dotenv::var;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::{
+    fs::read_to_string,
+    process::{Command, Stdio},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        OnceLock,
+    },
+};
+use tracing::debug;
+
+use crate::config::{self, Profile};
+
+/// The developer tokens, loaded on the first request.
+static TOKENS: OnceLock<Result<TokenPool, String>> = OnceLock::new();
+
+/// How requests are spread over several developer tokens.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenRotation {
+    /// Each request starts with the next token.
+    #[default]
+    RoundRobin,
+
+    /// Requests use the same token until it's rejected.
+    Failover,
+}
+
+impl FromStr for TokenRotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "round-robin" => Ok(Self::RoundRobin),
+            "failover" => Ok(Self::Failover),
+            _ => Err(format!(
+                "Unknown token rotation `{s}` (`round-robin` or `failover`)"
+            )),
+        }
+    }
+}
+
+/// The developer tokens requests are made with.
+/// A request rejected with a token (e.g. it's locked to another IP or over its
+/// rate limit) is retried with the others.
+pub struct TokenPool {
+    /// The tokens, without duplicates.
+    tokens: Vec<String>,
+
+    /// How requests are spread over the tokens.
+    rotation: TokenRotation,
+
+    /// The index of the token the next request starts with.
+    next: AtomicUsize,
+}
+
+impl TokenPool {
+    /// Gathers the tokens from the profile's `token`, `tokens`, `token_file`
+    /// and `token_command`, or from `TOKEN` in `.env` (comma-separated) if it
+    /// has none.
+    /// Returns an error if a source fails or there are no tokens.
+    pub fn load(profile: &Profile) -> Result<Self, String> {
+        let mut tokens: Vec<String> = profile
+            .token
+            .iter()
+            .chain(&profile.tokens)
+            .cloned()
+            .collect();
+
+        if let Some(path) = &profile.token_file {
+            let s =
+                read_to_string(path).map_err(|_| format!("Failed to read token file `{path}`"))?;
+            tokens.extend(parse_tokens(&s));
+        }
+
+        if let Some(command) = &profile.token_command {
+            tokens.extend(parse_tokens(&run_token_command(command)?));
+        }
+
+        if tokens.is_empty() {
+            if let Ok(s) = var("TOKEN") {
+                tokens.extend(s.split(',').map(|token| token.trim().to_string()));
+            }
+        }
+
+        let mut unique = Vec::new();
+        for token in tokens {
+            if !token.is_empty() && !unique.contains(&token) {
+                unique.push(token);
+            }
+        }
+        if unique.is_empty() {
+            return Err("No developer token found in the config or `.env`".to_string());
+        }
+
+        let rotation = profile.token_rotation.unwrap_or_default();
+        debug!(
+            tokens = unique.len(),
+            failover = rotation == TokenRotation::Failover,
+            "Loaded developer tokens"
+        );
+
+        Ok(Self {
+            tokens: unique,
+            rotation,
+            next: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the number of tokens.
+    fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns the tokens with their indices, in the order a request should
+    /// try them.
+    pub(super) fn order(&self) -> impl Iterator<Item = (usize, &str)> {
+        let start = match self.rotation {
+            TokenRotation::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed),
+            TokenRotation::Failover => self.next.load(Ordering::Relaxed),
+        } % self.len();
+
+        (0..self.len()).map(move |i| {
+            let index = (start + i) % self.len();
+            (index, self.tokens[index].as_str())
+        })
+    }
+
+    /// Moves failover requests on from the token, after it was rejected.
+    pub(super) fn reject(&self, index: usize) {
+        if self.rotation == TokenRotation::Failover {
+            // Another request may have moved on already.
+            let _ = self.next.compare_exchange(
+                index,
+                (index + 1) % self.len(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    /// Returns the text with every token replaced by `<token #N>`.
+    pub fn redact(&self, s: &str) -> String {
+        self.tokens
+            .iter()
+            .enumerate()
+            .fold(s.to_string(), |s, (i, token)| {
+                s.replace(token, &format!("<token #{}>", i + 1))
+            })
+    }
+}
+
+/// Returns the token pool, loading it from the config profile on the first
+/// call, or an error if it couldn't be loaded.
+pub fn pool() -> Result<&'static TokenPool, String> {
+    TOKENS
+        .get_or_init(|| TokenPool::load(config::profile()))
+        .as_ref()
+        .map_err(|e| e.clone())
+}
+
+/// Returns the text with any developer token replaced, for printing errors and
+/// logs.
+/// Tokens are only known once the pool has been loaded; before then nothing
+/// can contain one.
+pub fn redact(s: &str) -> String {
+    match TOKENS.get() {
+        Some(Ok(pool)) => pool.redact(s),
+        _ => s.to_string(),
+    }
+}
+
+/// Returns whether a response with the status means the token was rejected,
+/// so another one might succeed.
+pub(super) fn is_rejected(status: StatusCode) -> bool {
+    status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Returns the tokens listed one per line, skipping blank lines and `#`
+/// comments.
+fn parse_tokens(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+}
+
+/// Runs the command with the shell and returns what it printed, or an error if
+/// it fails.
+/// Its errors are shown as is rather than captured, so they aren't mixed into
+/// ours, and ours leave out the command, which may contain secrets.
+fn run_token_command(command: &str) -> Result<String, String> {
+    let output = Command::new("sh")
+        .args(["-c", command])
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| "Failed to run token command")?;
+
+    if !output.status.success() {
+        return Err(format!("Token command failed ({})", output.status));
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| "Token command printed invalid UTF-8".to_string())
+}
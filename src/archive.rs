@@ -0,0 +1,220 @@
+mod fetch;
+mod query;
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{from_str, to_string, Value};
+use std::env::Args;
+
+use crate::{models::battles::Battle, util::BAD_JSON_ERR_MSG};
+
+use fetch::process_fetch_commands;
+use query::process_query_commands;
+
+/// The battle archive's database file, if no other is given.
+pub const DEFAULT_ARCHIVE_PATH: &str = "battles.db";
+
+/// The archive's tables.
+/// Each battle is stored once, keyed by its time and sorted participant tags,
+/// no matter whose battle log it was fetched from.
+/// Snapshots are API responses (e.g. a player's profile) kept each time they
+/// change.
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS battles (
+    battle_time TEXT NOT NULL,
+    participants TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    game_mode TEXT,
+    json TEXT NOT NULL,
+    PRIMARY KEY (battle_time, participants)
+);
+CREATE TABLE IF NOT EXISTS snapshots (
+    kind TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    fetched_at TEXT NOT NULL,
+    json TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS snapshots_by_tag ON snapshots (kind, tag, fetched_at);";
+
+/// A local SQLite database of battles from players' battle logs.
+pub struct Archive {
+    connection: Connection,
+}
+
+/// Filters for battles in the archive.
+/// Filters left as `None` match every battle.
+#[derive(Default)]
+pub struct ArchiveQuery {
+    /// The tag of a player who was in the battle.
+    pub player: Option<String>,
+
+    /// The battle type (e.g. `pathOfLegend`) or game mode name (e.g. `Ladder`),
+    /// ignoring case.
+    pub mode: Option<String>,
+
+    /// The earliest battle time, in the API's time format or a prefix of it
+    /// (e.g. `20250101`).
+    pub from: Option<String>,
+
+    /// The battle time that all battles were before, in the same format as
+    /// `from`.
+    pub until: Option<String>,
+}
+
+impl Archive {
+    /// Opens the archive at the given path, creating it if it doesn't exist.
+    pub fn open(path: &str) -> Result<Self, String> {
+        let connection = Connection::open(path)
+            .map_err(|e| format!("Failed to open battle archive `{path}`: {e}"))?;
+        connection.execute_batch(SCHEMA).map_err(db_error)?;
+
+        Ok(Self { connection })
+    }
+
+    /// Adds the battles to the archive, updating any already in it.
+    /// Returns the number of battles that weren't in it before.
+    pub fn upsert(&mut self, battles: &[Battle]) -> Result<usize, String> {
+        let before = self.count()?;
+        let transaction = self.connection.transaction().map_err(db_error)?;
+
+        {
+            let mut statement = transaction
+                .prepare(
+                    "INSERT INTO battles (battle_time, participants, kind, game_mode, json)
+                    VALUES (?1, ?2, ?3, ?4, ?5)
+                    ON CONFLICT (battle_time, participants) DO UPDATE SET
+                        kind = excluded.kind,
+                        game_mode = excluded.game_mode,
+                        json = excluded.json",
+                )
+                .map_err(db_error)?;
+
+            for battle in battles {
+                let json = to_string(battle).map_err(|_| BAD_JSON_ERR_MSG)?;
+                statement
+                    .execute(params![
+                        battle.battle_time,
+                        battle.participants().join(","),
+                        battle.kind,
+                        battle.game_mode.as_ref().and_then(|mode| mode.name.clone()),
+                        json,
+                    ])
+                    .map_err(db_error)?;
+            }
+        }
+
+        transaction.commit().map_err(db_error)?;
+
+        Ok(self.count()? - before)
+    }
+
+    /// Returns the number of battles in the archive.
+    pub fn count(&self) -> Result<usize, String> {
+        self.connection
+            .query_row("SELECT COUNT(*) FROM battles", [], |row| row.get(0))
+            .map_err(db_error)
+    }
+
+    /// Returns the battles matching the query, from newest to oldest.
+    /// If the query has a player, the battles are from their perspective.
+    pub fn query(&self, query: &ArchiveQuery) -> Result<Vec<Battle>, String> {
+        let player = query
+            .player
+            .as_deref()
+            .map(|tag| format!("#{}", tag.trim_start_matches('#').to_uppercase()));
+
+        let mut statement = self
+            .connection
+            .prepare(
+                "SELECT json FROM battles
+                WHERE (?1 IS NULL OR ',' || participants || ',' LIKE '%,' || ?1 || ',%')
+                    AND (?2 IS NULL OR kind = ?2 COLLATE NOCASE OR game_mode = ?2 COLLATE NOCASE)
+                    AND (?3 IS NULL OR battle_time >= ?3)
+                    AND (?4 IS NULL OR battle_time < ?4)
+                ORDER BY battle_time DESC",
+            )
+            .map_err(db_error)?;
+        let rows = statement
+            .query_map(
+                params![player, query.mode, query.from, query.until],
+                |row| row.get::<_, String>(0),
+            )
+            .map_err(db_error)?;
+
+        let mut battles = Vec::new();
+        for json in rows {
+            let battle: Battle = from_str(&json.map_err(db_error)?)
+                .map_err(|_| "Found a bad battle in the archive".to_string())?;
+            match &player {
+                Some(tag) => battles.extend(battle.oriented(tag)),
+                None => battles.push(battle),
+            }
+        }
+
+        Ok(battles)
+    }
+
+    /// Returns the latest snapshot of the given kind (e.g. `player`) for the
+    /// tag, or `None` if there isn't one.
+    pub fn latest_snapshot(&self, kind: &str, tag: &str) -> Result<Option<Value>, String> {
+        let json: Option<String> = self
+            .connection
+            .query_row(
+                "SELECT json FROM snapshots WHERE kind = ?1 AND tag = ?2
+                ORDER BY fetched_at DESC LIMIT 1",
+                params![kind, tag.trim_start_matches('#')],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)?;
+
+        json.map(|json| {
+            from_str(&json).map_err(|_| "Found a bad snapshot in the archive".to_string())
+        })
+        .transpose()
+    }
+
+    /// Stores the snapshot of the given kind for the tag if it differs from the
+    /// latest one.
+    /// Returns `true` if it was stored.
+    pub fn add_snapshot(&self, kind: &str, tag: &str, json: &Value) -> Result<bool, String> {
+        if self.latest_snapshot(kind, tag)?.as_ref() == Some(json) {
+            return Ok(false);
+        }
+
+        let s = to_string(json).map_err(|_| BAD_JSON_ERR_MSG)?;
+        self.connection
+            .execute(
+                "INSERT INTO snapshots (kind, tag, fetched_at, json) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    kind,
+                    tag.trim_start_matches('#'),
+                    Utc::now().to_rfc3339(),
+                    s
+                ],
+            )
+            .map_err(db_error)?;
+
+        Ok(true)
+    }
+}
+
+/// Parses the input arguments after `archive` and prints the output, or
+/// returns an error if any are encountered.
+pub fn process_archive_commands(mut args: Args) -> Result<(), String> {
+    match args.next().as_deref() {
+        // Store players' recent battles.
+        Some("fetch") => process_fetch_commands(args),
+
+        // Search the stored battles.
+        Some("query") => process_query_commands(args),
+
+        // Errors:
+        Some(s) => Err(format!("Unexpected input: `{s}`")),
+        None => Err("Expected archive command (`fetch` or `query`)".to_string()),
+    }
+}
+
+/// Returns an error message for the database error.
+fn db_error(e: rusqlite::Error) -> String {
+    format!("Battle archive error: {e}")
+}
@@ -0,0 +1,54 @@
+use std::env::Args;
+
+use crate::{
+    api::{api_get, endpoints},
+    config,
+    models::battles::Battle,
+};
+
+use super::{Archive, DEFAULT_ARCHIVE_PATH};
+
+/// Parses the input arguments after `archive fetch`, stores the players'
+/// recent battles in the archive and prints how many were new, or returns an
+/// error if any are encountered.
+pub fn process_fetch_commands(mut args: Args) -> Result<(), String> {
+    let mut tags = Vec::new();
+    let mut path = DEFAULT_ARCHIVE_PATH.to_string();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The archive's database file.
+            "--db" => path = args.next().ok_or("Expected archive file")?,
+
+            // Errors:
+            _ if arg.starts_with('-') => return Err(format!("Unexpected input: `{arg}`")),
+
+            // The players whose battles to store.
+            _ => tags.push(arg),
+        }
+    }
+
+    if tags.is_empty() {
+        return Err("Expected player IDs".to_string());
+    }
+
+    let tags = config::profile().expand_tags(&tags)?;
+    let mut archive = Archive::open(&path)?;
+    let mut output = format!("- Archived battles to `{path}`:");
+
+    for tag in &tags {
+        let battles: Vec<Battle> = api_get(&endpoints::battle_log(tag))?;
+        let new = archive.upsert(&battles)?;
+
+        output.push_str(&format!(
+            "\n\t#{}: {} battles, {new} new",
+            tag.trim_start_matches('#'),
+            battles.len()
+        ));
+    }
+    output.push_str(&format!("\n\tTotal: {} battles", archive.count()?));
+
+    println!("{output}");
+
+    Ok(())
+}
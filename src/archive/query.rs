@@ -0,0 +1,181 @@
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+use serde_json::to_value;
+use std::env::Args;
+
+use crate::{
+    cards::Card,
+    config::render,
+    models::battles::{Battle, BattlePlayer},
+    util::{next_number, parse_api_time, write_json, BAD_JSON_ERR_MSG},
+};
+
+use super::{Archive, ArchiveQuery, DEFAULT_ARCHIVE_PATH};
+
+/// The format of dates given to `--from` and `--to`.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The format of battle times in the output.
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+/// The number of battles listed, if no number is given.
+const DEFAULT_LIMIT: usize = 25;
+
+/// The archived battles matching a query.
+#[derive(Serialize)]
+struct ArchivedBattles {
+    /// The number of battles found.
+    found: usize,
+
+    /// The battles won, lost and drawn.
+    wins: usize,
+    losses: usize,
+    draws: usize,
+
+    /// The first battles found, up to the limit.
+    battles: Vec<Battle>,
+}
+
+/// Parses the input arguments after `archive query` and prints the matching
+/// battles, or returns an error if any are encountered.
+pub fn process_query_commands(mut args: Args) -> Result<(), String> {
+    let mut path = DEFAULT_ARCHIVE_PATH.to_string();
+    let mut query = ArchiveQuery::default();
+    let mut deck = Vec::new();
+    let mut opponent = None;
+    let mut limit = DEFAULT_LIMIT;
+    let mut out_path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The archive's database file.
+            "--db" => path = args.next().ok_or("Expected archive file")?,
+
+            // Filters.
+            "--player" => query.player = Some(args.next().ok_or("Expected player ID")?),
+            "--mode" => query.mode = Some(args.next().ok_or("Expected battle type or mode")?),
+            "--from" => query.from = Some(parse_date(args.next(), 0)?),
+            "--to" => query.until = Some(parse_date(args.next(), 1)?),
+            "--deck" => {
+                let names = args.next().ok_or("Expected card names")?;
+                deck = names
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|name| !name.is_empty())
+                    .map(Card::from_name)
+                    .collect::<Result<_, _>>()?;
+            }
+            "--opponent" => opponent = Some(args.next().ok_or("Expected opponent ID or name")?),
+
+            // How many battles to list.
+            "--limit" => limit = next_number(&mut args, "battle limit")?,
+
+            // Write the matching battles to a file, e.g. as a snapshot for
+            // `matchups`.
+            "--out" => out_path = Some(args.next().ok_or("Expected output file")?),
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let battles: Vec<_> = Archive::open(&path)?
+        .query(&query)?
+        .into_iter()
+        .filter(|battle| has_cards(&battle.team, &deck))
+        .filter(|battle| {
+            opponent
+                .as_deref()
+                .is_none_or(|opponent| battle.opponent.iter().any(|p| is_player(p, opponent)))
+        })
+        .collect();
+
+    if let Some(out_path) = &out_path {
+        let json = to_value(&battles).map_err(|_| BAD_JSON_ERR_MSG)?;
+        write_json(&json, out_path)?;
+    }
+
+    let results: Vec<_> = battles.iter().map(Battle::won).collect();
+    let count = |result| results.iter().filter(|&&r| r == result).count();
+    let report = ArchivedBattles {
+        found: battles.len(),
+        wins: count(Some(true)),
+        losses: count(Some(false)),
+        draws: count(None),
+        battles: battles.into_iter().take(limit).collect(),
+    };
+
+    println!("{}", render(&report, get_battles_info)?);
+
+    Ok(())
+}
+
+/// Parses the date (e.g. `2025-01-31`) into the API's time format, after
+/// adding the given number of days.
+fn parse_date(date: Option<String>, days: u64) -> Result<String, String> {
+    let date = date.ok_or("Expected date (YYYY-MM-DD)")?;
+    let parsed = NaiveDate::parse_from_str(&date, DATE_FORMAT)
+        .ok()
+        .and_then(|parsed| parsed.checked_add_days(Days::new(days)))
+        .ok_or_else(|| format!("Expected date (YYYY-MM-DD), got `{date}`"))?;
+
+    Ok(parsed.format("%Y%m%d").to_string())
+}
+
+/// Returns `true` if any of the players used all of the cards.
+fn has_cards(players: &[BattlePlayer], cards: &[&Card]) -> bool {
+    players.iter().any(|player| {
+        cards
+            .iter()
+            .all(|card| player.cards.iter().any(|owned| owned.id == card.id))
+    })
+}
+
+/// Returns `true` if the player has the given tag or name (ignoring case).
+fn is_player(player: &BattlePlayer, id: &str) -> bool {
+    player
+        .tag
+        .trim_start_matches('#')
+        .eq_ignore_ascii_case(id.trim_start_matches('#'))
+        || player.name.eq_ignore_ascii_case(id)
+}
+
+/// Returns a string containing the battles' record and the listed battles.
+fn get_battles_info(report: &ArchivedBattles) -> String {
+    let mut output = format!(
+        "- Archived Battles ({} found, {}W {}L {}D):",
+        report.found, report.wins, report.losses, report.draws
+    );
+
+    for battle in &report.battles {
+        let names = |players: &[BattlePlayer]| {
+            let names: Vec<_> = players.iter().map(|p| p.name.as_str()).collect();
+            names.join(" & ")
+        };
+        let crowns = |players: &[BattlePlayer]| players.iter().map(|p| p.crowns).max().unwrap_or(0);
+        let mode = battle
+            .game_mode
+            .as_ref()
+            .map(|mode| mode.display_name())
+            .unwrap_or_else(|| battle.kind.clone());
+        let result = match battle.won() {
+            Some(true) => "Win",
+            Some(false) => "Loss",
+            None => "Draw",
+        };
+        // Show an invalid time as is rather than failing the whole listing.
+        let time = parse_api_time(&battle.battle_time).map_or_else(
+            |_| battle.battle_time.clone(),
+            |time| time.format(TIME_FORMAT).to_string(),
+        );
+
+        output.push_str(&format!(
+            "\n\t{time} {mode}: {} {}-{} {} ({result})",
+            names(&battle.team),
+            crowns(&battle.team),
+            crowns(&battle.opponent),
+            names(&battle.opponent)
+        ));
+    }
+
+    output
+}
@@ -1,5 +1,9 @@
-use crate::util::is_kebab_case;
+use crate::{
+    models::cards::{CardCatalog, IconUrls},
+    util::is_kebab_case,
+};
 
+#[derive(Debug)]
 pub struct Card {
     /// The card's name.
     pub name: &'static str,
@@ -13,12 +17,28 @@ pub struct Card {
 
 pub const NUM_CARDS: usize = 121;
 
+/// The offset from the API's card levels (relative to `maxLevel`) to in-game
+/// levels.
+const LEVEL_OFFSET: i64 = 14;
+
 macro_rules! def_cards {
     ($($name:ident: $card_name:expr, $badge_name:expr, $id: expr);* ;) => {
         $(const $name: Card = Card::new($card_name, $badge_name, $id);)*
+
+        /// Every card, in order of ID.
+        pub const ALL: [&'static Card; NUM_CARDS] = [$(&Self::$name),*];
     };
 }
 
+/// Returns a card's level as shown in-game, from the API's `level` and
+/// `maxLevel`.
+///
+/// The API's levels are relative to the card's rarity; they're the number of
+/// times the card has been leveled up (minus one).
+pub fn normalize_level(level: i64, max_level: i64) -> i64 {
+    (level - max_level) + LEVEL_OFFSET
+}
+
 impl Card {
     /// Returns a card with the given name badge name and ID.
     const fn new(name: &'static str, badge_name: &'static str, id: i64) -> Self {
@@ -164,6 +184,27 @@ impl Card {
         })
     }
 
+    /// Returns the card's kebab-case name, as `from_name` takes it.
+    pub fn kebab_name(&self) -> String {
+        self.name
+            .to_lowercase()
+            .replace(' ', "-")
+            .chars()
+            .filter(|&c| c.is_ascii_alphanumeric() || c == '-')
+            .collect()
+    }
+
+    /// Returns the card with the given ID, or `None` if there isn't one.
+    pub fn from_id(id: i64) -> Option<&'static Self> {
+        Self::ALL.into_iter().find(|card| card.id == id)
+    }
+
+    /// Returns the card's icon URLs from the API's card catalog, or `None` if
+    /// the catalog doesn't have the card.
+    pub fn icon_urls<'a>(&self, catalog: &'a CardCatalog) -> Option<&'a IconUrls> {
+        catalog.get(self.id).map(|card| &card.icon_urls)
+    }
+
     def_cards!(
         KNIGHT: "Knight", "MasteryKnight", 26000000;
         ARCHERS: "Archers", "MasteryArchers", 26000001;
@@ -0,0 +1,59 @@
+use std::env::Args;
+
+use crate::{
+    api::{api_get, endpoints},
+    cards::NUM_CARDS,
+    config,
+    models::cards::CardCatalog,
+};
+
+/// Parses the input arguments after `catalog` and prints the output, or
+/// returns an error if any are encountered.
+pub fn process_catalog_commands(mut args: Args) -> Result<(), String> {
+    config::require_text("catalog")?;
+
+    let output = match args.next().as_deref() {
+        // Compare the API's cards against the compiled ones.
+        Some("check") => get_catalog_check()?,
+
+        // Errors:
+        Some(s) => return Err(format!("Unexpected input: `{s}`")),
+        None => return Err("Expected catalog command (`check`)".to_string()),
+    };
+
+    println!("{output}");
+
+    Ok(())
+}
+
+/// Gets the API's card catalog and returns a string listing its differences
+/// from the compiled cards.
+fn get_catalog_check() -> Result<String, String> {
+    let catalog: CardCatalog = api_get(&endpoints::cards())?;
+    let diff = catalog.diff();
+    let mut output = format!(
+        "- Card catalog: {} cards in the API, {NUM_CARDS} compiled",
+        catalog.items.len()
+    );
+
+    if diff.missing.is_empty() && diff.removed.is_empty() {
+        output.push_str("\n\tUp to date");
+        return Ok(output);
+    }
+
+    for card in diff.missing {
+        output.push_str(&format!(
+            "\n\tNot compiled: \"{}\" ({}, {})",
+            card.name, card.id, card.rarity
+        ));
+    }
+    for card in diff.removed {
+        output.push_str(&format!(
+            "\n\tNot in the API: \"{}\" ({})",
+            card.name, card.id
+        ));
+    }
+    output.push_str("\n\tRegenerate the compiled cards with `util/gen_cards.py`");
+
+    Ok(output)
+}
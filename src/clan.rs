@@ -0,0 +1,30 @@
+mod audit;
+mod search;
+pub mod war;
+
+use std::env::Args;
+
+use audit::process_audit_commands;
+use search::process_search_commands;
+use war::process_war_commands;
+
+pub use audit::{AuditReport, Thresholds};
+
+/// Parses the input arguments after `clan` and prints the output, or returns
+/// an error if any are encountered.
+pub fn process_clan_commands(mut args: Args) -> Result<(), String> {
+    match args.next().as_deref() {
+        // Search for clans.
+        Some("search") => process_search_commands(args),
+
+        // River race report.
+        Some("war") => process_war_commands(args),
+
+        // Members to kick or promote.
+        Some("audit") => process_audit_commands(args),
+
+        // Errors:
+        Some(s) => Err(format!("Unexpected input: `{s}`")),
+        None => Err("Expected clan command (`search`, `war` or `audit`)".to_string()),
+    }
+}
@@ -0,0 +1,267 @@
+use chrono::Utc;
+use serde::Serialize;
+use std::{cmp::Reverse, env::Args};
+
+use crate::{
+    config::render,
+    models::{clans::ClanMember, river_race::RiverRaceLogEntry},
+    util::{next_number, parse_api_time},
+};
+
+use super::war::{get_members, get_race_log, DECKS_PER_DAY, WAR_DAYS};
+
+/// The thresholds members are audited against.
+#[derive(Serialize)]
+pub struct Thresholds {
+    /// The most days a member can go without being online.
+    pub inactive_days: u32,
+
+    /// The fewest cards a member should donate per week.
+    pub min_donations: u32,
+
+    /// The fewest war decks a member should use per race, on average.
+    pub min_war_decks: u32,
+
+    /// The number of past races war participation is averaged over.
+    pub weeks: u32,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            inactive_days: 7,
+            min_donations: 50,
+            min_war_decks: DECKS_PER_DAY * WAR_DAYS * 3 / 4,
+            weeks: 2,
+        }
+    }
+}
+
+/// A member's audited activity.
+#[derive(Serialize)]
+pub struct Audit {
+    /// The member.
+    pub member: ClanMember,
+
+    /// The days since the member was last online.
+    pub days_inactive: i64,
+
+    /// The number of past races the member was in the clan for.
+    pub races: usize,
+
+    /// The average war decks the member used per past race they were in, or
+    /// `None` if they weren't in any.
+    pub war_decks: Option<f64>,
+
+    /// The average fame the member earned per past race they were in, or
+    /// `None` if they weren't in any.
+    pub fame: Option<f64>,
+
+    /// The thresholds the member falls short of, as reasons to kick them.
+    pub reasons: Vec<String>,
+}
+
+/// The members of a clan recommended to kick and promote.
+#[derive(Serialize)]
+pub struct AuditReport {
+    /// The clan's tag, with the leading `#`.
+    pub clan: String,
+
+    /// The thresholds the members were audited against.
+    pub thresholds: Thresholds,
+
+    /// The number of past races war participation was averaged over.
+    pub races: usize,
+
+    /// The members recommended to kick, with the most reasons (then longest
+    /// inactive) first.
+    pub kick: Vec<Audit>,
+
+    /// The plain members recommended to promote to elder, with the most fame
+    /// (then donations) first.
+    /// Only members who used every war deck and meet all thresholds are listed.
+    pub promote: Vec<Audit>,
+}
+
+impl AuditReport {
+    /// Audits the clan's members against the thresholds using its past races,
+    /// or returns an error if the data is invalid.
+    pub fn new(
+        tag: &str,
+        members: Vec<ClanMember>,
+        log: &[RiverRaceLogEntry],
+        thresholds: Thresholds,
+    ) -> Result<Self, String> {
+        let clan = format!("#{}", tag.trim_start_matches('#'));
+        let audits = members
+            .into_iter()
+            .map(|member| audit_member(member, &clan, log, &thresholds))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (mut kick, rest): (Vec<_>, Vec<_>) =
+            audits.into_iter().partition(|a| !a.reasons.is_empty());
+        kick.sort_by_key(|a| (Reverse(a.reasons.len()), Reverse(a.days_inactive)));
+
+        let mut promote: Vec<_> = rest
+            .into_iter()
+            .filter(|a| {
+                a.member.role == "member"
+                    && a.war_decks
+                        .is_some_and(|decks| decks >= (DECKS_PER_DAY * WAR_DAYS) as f64)
+            })
+            .collect();
+        promote.sort_by_key(|a| {
+            (
+                Reverse(a.fame.unwrap_or_default() as u32),
+                Reverse(a.member.donations),
+            )
+        });
+
+        Ok(Self {
+            clan,
+            thresholds,
+            races: log.len(),
+            kick,
+            promote,
+        })
+    }
+
+    /// Returns the report as the CLI prints it.
+    pub fn describe(&self) -> String {
+        let mut output = format!(
+            "- Clan {} Audit ({} days inactive, {} donations, {} war decks over {} weeks):",
+            self.clan,
+            self.thresholds.inactive_days,
+            self.thresholds.min_donations,
+            self.thresholds.min_war_decks,
+            self.races
+        );
+        output.push_str(get_kick_info(&self.kick).as_str());
+        output.push_str(get_promote_info(&self.promote).as_str());
+
+        output
+    }
+}
+
+/// Parses the input arguments after `clan audit` and prints the recommended
+/// members to kick and promote, or returns an error if any are encountered.
+pub fn process_audit_commands(mut args: Args) -> Result<(), String> {
+    let tag = args.next().ok_or("Expected clan tag")?;
+    let mut thresholds = Thresholds::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inactive-days" => thresholds.inactive_days = next_number(&mut args, "days")?,
+            "--min-donations" => thresholds.min_donations = next_number(&mut args, "donations")?,
+            "--min-war-decks" => thresholds.min_war_decks = next_number(&mut args, "war decks")?,
+            "--weeks" => thresholds.weeks = next_number(&mut args, "number of weeks")?,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let members = get_members(&tag)?;
+    let log = get_race_log(&tag, thresholds.weeks)?;
+
+    let report = AuditReport::new(&tag, members, &log, thresholds)?;
+    println!("{}", render(&report, AuditReport::describe)?);
+
+    Ok(())
+}
+
+/// Audits the member's activity against the thresholds, or returns an error if
+/// their data is invalid.
+fn audit_member(
+    member: ClanMember,
+    clan_tag: &str,
+    log: &[RiverRaceLogEntry],
+    thresholds: &Thresholds,
+) -> Result<Audit, String> {
+    let days_inactive = (Utc::now() - parse_api_time(&member.last_seen)?).num_days();
+
+    // Sum the member's participation over the past races they were in the clan
+    // for, which are the ones they're listed in.
+    let (races, decks, fame) = log
+        .iter()
+        .filter_map(|race| race.standing(clan_tag))
+        .filter_map(|standing| {
+            standing
+                .clan
+                .participants
+                .iter()
+                .find(|participant| participant.tag == member.tag)
+        })
+        .fold((0, 0, 0), |(races, decks, fame), participant| {
+            (
+                races + 1,
+                decks + participant.decks_used,
+                fame + participant.fame,
+            )
+        });
+    let (war_decks, fame) = if races == 0 {
+        (None, None)
+    } else {
+        (
+            Some(decks as f64 / races as f64),
+            Some(fame as f64 / races as f64),
+        )
+    };
+
+    let mut reasons = Vec::new();
+    if days_inactive > i64::from(thresholds.inactive_days) {
+        reasons.push(format!("inactive {days_inactive} days"));
+    }
+    if member.donations < thresholds.min_donations {
+        reasons.push(format!("{} donations", member.donations));
+    }
+    // Members who weren't in any of the races (e.g. who just joined) aren't
+    // judged on them.
+    if let Some(decks) = war_decks.filter(|&decks| decks < thresholds.min_war_decks as f64) {
+        reasons.push(format!("{decks:.1} war decks per week"));
+    }
+
+    Ok(Audit {
+        member,
+        days_inactive,
+        races,
+        war_decks,
+        fame,
+        reasons,
+    })
+}
+
+/// Returns a string listing the members recommended to kick.
+fn get_kick_info(kicks: &[Audit]) -> String {
+    let mut output = format!("\n\tKick ({}):", kicks.len());
+    for (i, audit) in kicks.iter().enumerate() {
+        output.push_str(&format!(
+            "\n\t\t{}. \"{}\" ({}, {}): {}",
+            i + 1,
+            audit.member.name,
+            audit.member.tag,
+            audit.member.role,
+            audit.reasons.join("; ")
+        ));
+    }
+
+    output
+}
+
+/// Returns a string listing the members recommended to promote.
+fn get_promote_info(promotions: &[Audit]) -> String {
+    let mut output = format!("\n\tPromote ({}):", promotions.len());
+    for (i, audit) in promotions.iter().enumerate() {
+        output.push_str(&format!(
+            "\n\t\t{}. \"{}\" ({}): {:.0} fame per week, {} donations, {:.1} war decks per week",
+            i + 1,
+            audit.member.name,
+            audit.member.tag,
+            audit.fame.unwrap_or_default(),
+            audit.member.donations,
+            audit.war_decks.unwrap_or_default()
+        ));
+    }
+
+    output
+}
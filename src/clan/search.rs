@@ -0,0 +1,189 @@
+use serde::Serialize;
+use std::{cmp::Reverse, env::Args};
+
+use crate::{
+    api::{
+        endpoints::{self, ClanSearchQuery, PageQuery},
+        paging::{paginate, Paginated, DEFAULT_PAGE_SIZE},
+    },
+    config::render,
+    models::clans::ClanSummary,
+    rankings::find_location,
+    util::next_number,
+};
+
+/// The number of clans listed if no limit is given.
+const DEFAULT_LIMIT: u32 = 25;
+
+/// The most results fetched to sort, since a broad search can have thousands.
+const MAX_SORTED_RESULTS: u32 = 1000;
+
+/// The clans found by a search.
+#[derive(Serialize)]
+struct SearchResults {
+    /// The clans, in the order listed.
+    clans: Vec<ClanSummary>,
+
+    /// The cursor to continue after the clans, if there are more.
+    after: Option<String>,
+
+    /// Whether only the first `MAX_SORTED_RESULTS` results were sorted.
+    sort_capped: bool,
+}
+
+/// What to sort the search results by, from highest to lowest.
+enum SortBy {
+    /// Clan score.
+    Score,
+
+    /// Number of members.
+    Members,
+
+    /// Clan war trophies.
+    WarTrophies,
+}
+
+/// Parses the input arguments after `clan search`, searches for clans and
+/// prints the results, or returns an error if any are encountered.
+pub fn process_search_commands(mut args: Args) -> Result<(), String> {
+    let mut query = ClanSearchQuery::default();
+    let mut limit = DEFAULT_LIMIT;
+    let mut after = None;
+    let mut sort_by = None;
+    let mut max_required_trophies = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Search filters.
+            "--name" => query.name = Some(args.next().ok_or("Expected clan name")?),
+            "--location" => {
+                let location = args.next().ok_or("Expected location name or code")?;
+                query.location_id = Some(find_location_id(&location)?);
+            }
+            "--min-members" => query.min_members = Some(next_number(&mut args, "minimum members")?),
+            "--max-members" => query.max_members = Some(next_number(&mut args, "maximum members")?),
+            "--min-score" => query.min_score = Some(next_number(&mut args, "minimum score")?),
+            "--trophies" => max_required_trophies = Some(next_number(&mut args, "trophy count")?),
+
+            // Output.
+            "--sort" => {
+                sort_by = Some(match args.next().as_deref() {
+                    Some("score") => SortBy::Score,
+                    Some("members") => SortBy::Members,
+                    Some("war-trophies") => SortBy::WarTrophies,
+                    _ => return Err("Expected `score`, `members` or `war-trophies`".to_string()),
+                })
+            }
+            "--limit" => limit = next_number(&mut args, "result limit")?,
+            "--after" => after = Some(args.next().ok_or("Expected cursor")?),
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let url = endpoints::clan_search(&query)?;
+    let can_join = |clan: &Result<ClanSummary, String>| match (clan, max_required_trophies) {
+        (Ok(clan), Some(trophies)) => clan.required_trophies <= trophies,
+        _ => true,
+    };
+
+    let results = match sort_by {
+        // Sorting needs every result, so only a capped number are fetched and
+        // there's nothing to continue after.
+        Some(sort_by) => {
+            let page = PageQuery {
+                limit: Some(MAX_SORTED_RESULTS),
+                after,
+                before: None,
+            };
+            let mut pages: Paginated<ClanSummary> = paginate(&url, &page);
+            let mut clans = pages
+                .by_ref()
+                .filter(can_join)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            match sort_by {
+                SortBy::Score => clans.sort_by_key(|clan| Reverse(clan.clan_score)),
+                SortBy::Members => clans.sort_by_key(|clan| Reverse(clan.members)),
+                SortBy::WarTrophies => clans.sort_by_key(|clan| Reverse(clan.clan_war_trophies)),
+            }
+            clans.truncate(limit as usize);
+
+            SearchResults {
+                clans,
+                after: None,
+                sort_capped: pages.next_cursor().is_some(),
+            }
+        }
+
+        // Pages are as big as the limit, so without a trophy filter the cursor
+        // continues right after the listed clans. With one, any clans left
+        // unchecked on the last page are skipped.
+        None => {
+            let page = PageQuery {
+                after,
+                ..Default::default()
+            };
+            let mut pages: Paginated<ClanSummary> =
+                paginate(&url, &page).page_size(limit.min(DEFAULT_PAGE_SIZE));
+            let clans = pages
+                .by_ref()
+                .filter(can_join)
+                .take(limit as usize)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            SearchResults {
+                clans,
+                after: pages.next_cursor().map(str::to_string),
+                sort_capped: false,
+            }
+        }
+    };
+
+    println!("{}", render(&results, get_search_results)?);
+
+    Ok(())
+}
+
+/// Looks up the location by its name, country code or ID and returns its ID,
+/// or an error if there's no such location.
+fn find_location_id(query: &str) -> Result<i64, String> {
+    let (id, name) = find_location(query)?;
+    id.parse()
+        .map_err(|_| format!("Clans can't be searched for by location \"{name}\""))
+}
+
+/// Returns a string listing the clans found, with how to get more.
+fn get_search_results(results: &SearchResults) -> String {
+    let mut output = format!("- Found {} clans:", results.clans.len());
+
+    for clan in &results.clans {
+        let location = clan
+            .location
+            .as_ref()
+            .map_or("No location", |location| location.name.as_str());
+        output.push_str(&format!(
+            "\n\t\"{}\" ({}): {} score, {} war trophies, {}/50 members, {}, {} trophies required ({})",
+            clan.name,
+            clan.tag,
+            clan.clan_score,
+            clan.clan_war_trophies,
+            clan.members,
+            location,
+            clan.required_trophies,
+            clan.kind,
+        ));
+    }
+
+    if let Some(after) = &results.after {
+        output.push_str(&format!("\n- More results with `--after {after}`"));
+    }
+    if results.sort_capped {
+        output.push_str(&format!(
+            "\n- Only the first {MAX_SORTED_RESULTS} results were sorted; narrow the search to sort the rest"
+        ));
+    }
+
+    output
+}
@@ -0,0 +1,348 @@
+use serde::Serialize;
+use std::{cmp::Reverse, env::Args};
+
+use crate::{
+    api::{
+        api_get,
+        endpoints::{self, PageQuery},
+        paging::paginate,
+    },
+    config::render,
+    models::{
+        clans::ClanMember,
+        river_race::{CurrentRiverRace, RiverRaceLogEntry, RiverRaceParticipant},
+    },
+    util::next_number,
+};
+
+/// The number of war decks each member can use per day.
+pub const DECKS_PER_DAY: u32 = 4;
+
+/// The number of war days (not training days) in a river race.
+pub const WAR_DAYS: u32 = 4;
+
+/// The number of past races compared if no number is given.
+const DEFAULT_RACES: u32 = 4;
+
+/// A clan's current river race and its members' part in it.
+#[derive(Serialize)]
+struct WarReport {
+    /// The clan's name.
+    clan: String,
+
+    /// The week of the season, starting at `1`.
+    week: u32,
+
+    /// The day of the race, starting at `1`.
+    day: u32,
+
+    /// The kind of day (`training`, `warDay` or `colosseum`).
+    period_type: String,
+
+    /// Every clan in the race, by most fame.
+    standings: Vec<Standing>,
+
+    /// Each current member's participation, by most fame.
+    members: Vec<MemberParticipation>,
+
+    /// The current members who haven't used all of today's war decks, or
+    /// `None` on a training day.
+    unused_decks: Option<Vec<UnusedDecks>>,
+
+    /// The past races compared, oldest first (e.g. `S120W2`).
+    past_races: Vec<String>,
+
+    /// Each current member's fame in the past races.
+    fame_history: Vec<FameHistory>,
+}
+
+/// A clan's standing in the race.
+#[derive(Serialize)]
+struct Standing {
+    /// The clan's name.
+    name: String,
+
+    /// The clan's fame.
+    fame: u32,
+
+    /// The clan's repair points.
+    repair_points: u32,
+}
+
+/// A current member's participation in the race.
+#[derive(Serialize)]
+struct MemberParticipation {
+    /// The member's username.
+    name: String,
+
+    /// The member's tag, with the leading `#`.
+    tag: String,
+
+    /// The member's participation, or `None` if they haven't taken part.
+    participation: Option<RiverRaceParticipant>,
+}
+
+/// A current member's war decks used today.
+#[derive(Serialize)]
+struct UnusedDecks {
+    /// The member's username.
+    name: String,
+
+    /// The war decks they used today.
+    used: u32,
+}
+
+/// A current member's fame in the past races.
+#[derive(Serialize)]
+struct FameHistory {
+    /// The member's username.
+    name: String,
+
+    /// The member's fame in each race, oldest first, or `None` where they
+    /// weren't in it.
+    fames: Vec<Option<u32>>,
+
+    /// The member's average fame over the races they were in.
+    average: f64,
+}
+
+impl WarReport {
+    /// Returns the report as the CLI prints it.
+    fn describe(&self) -> String {
+        let mut output = format!(
+            "- \"{}\" River Race (week {}, day {}, {}):",
+            self.clan, self.week, self.day, self.period_type,
+        );
+        for (i, clan) in self.standings.iter().enumerate() {
+            output.push_str(&format!(
+                "\n\t#{} \"{}\": {} fame, {} repair points",
+                i + 1,
+                clan.name,
+                clan.fame,
+                clan.repair_points
+            ));
+        }
+
+        output.push_str("\n\tMembers:");
+        for member in &self.members {
+            let info = match &member.participation {
+                Some(p) => format!(
+                    "{} fame, {} repair points, {} boat attacks, {}/{DECKS_PER_DAY} decks today, {} decks this week",
+                    p.fame, p.repair_points, p.boat_attacks, p.decks_used_today, p.decks_used
+                ),
+                None => "Not participated".to_string(),
+            };
+            output.push_str(&format!(
+                "\n\t\t\"{}\" ({}): {info}",
+                member.name, member.tag
+            ));
+        }
+
+        output.push_str(&match &self.unused_decks {
+            None => "\n\tNo war decks to use on a training day".to_string(),
+            Some(unused) if unused.is_empty() => {
+                "\n\tAll members used their war decks today".to_string()
+            }
+            Some(unused) => {
+                let unused: Vec<_> = unused
+                    .iter()
+                    .map(|member| format!("\"{}\" ({}/{DECKS_PER_DAY})", member.name, member.used))
+                    .collect();
+                format!(
+                    "\n\tUnused war decks today ({}): {}",
+                    unused.len(),
+                    unused.join(", ")
+                )
+            }
+        });
+
+        if self.past_races.is_empty() {
+            output.push_str("\n\tNo past races");
+            return output;
+        }
+        output.push_str(&format!(
+            "\n\tFame in past races ({}):",
+            self.past_races.join(", ")
+        ));
+        for member in &self.fame_history {
+            let fames: Vec<_> = member
+                .fames
+                .iter()
+                .map(|fame| fame.map_or("-".to_string(), |fame| fame.to_string()))
+                .collect();
+            output.push_str(&format!(
+                "\n\t\t\"{}\": {} ({:.0} average)",
+                member.name,
+                fames.join(", "),
+                member.average
+            ));
+        }
+
+        output
+    }
+}
+
+/// Parses the input arguments after `clan war` and prints the clan's river race
+/// report, or returns an error if any are encountered.
+pub fn process_war_commands(mut args: Args) -> Result<(), String> {
+    let tag = args.next().ok_or("Expected clan tag")?;
+    let mut races = DEFAULT_RACES;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The number of past races to compare fame across.
+            "--races" => races = next_number(&mut args, "number of races")?,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let members = get_members(&tag)?;
+    let race: CurrentRiverRace = api_get(&endpoints::current_river_race(&tag))?;
+    let log = get_race_log(&tag, races)?;
+
+    let (past_races, fame_history) = get_fame_history(&race.clan.tag, &log, &members);
+    let report = WarReport {
+        clan: race.clan.name.clone(),
+        week: race.section_index + 1,
+        day: race.period_index % 7 + 1,
+        period_type: race.period_type.clone(),
+        standings: get_standings(&race),
+        members: get_participation(&race, &members),
+        unused_decks: get_unused_decks(&race, &members),
+        past_races,
+        fame_history,
+    };
+
+    println!("{}", render(&report, WarReport::describe)?);
+
+    Ok(())
+}
+
+/// Gets and returns every member of the clan with the given tag.
+pub fn get_members(tag: &str) -> Result<Vec<ClanMember>, String> {
+    paginate(&endpoints::clan_members(tag), &PageQuery::default()).collect()
+}
+
+/// Gets and returns the clan's last `races` finished river races, newest first.
+pub fn get_race_log(tag: &str, races: u32) -> Result<Vec<RiverRaceLogEntry>, String> {
+    let page = PageQuery {
+        limit: Some(races),
+        ..Default::default()
+    };
+
+    paginate(&endpoints::river_race_log(tag), &page).collect()
+}
+
+/// Returns the participation of the member in the race, or `None` if they
+/// haven't taken part.
+fn find_participant<'a>(
+    race: &'a CurrentRiverRace,
+    member: &ClanMember,
+) -> Option<&'a RiverRaceParticipant> {
+    race.clan
+        .participants
+        .iter()
+        .find(|participant| participant.tag == member.tag)
+}
+
+/// Returns every clan's standing in the race, this one included, by most
+/// fame.
+fn get_standings(race: &CurrentRiverRace) -> Vec<Standing> {
+    let mut clans: Vec<_> = race.clans.iter().collect();
+    clans.sort_by_key(|clan| Reverse(clan.fame));
+
+    clans
+        .into_iter()
+        .map(|clan| Standing {
+            name: clan.name.clone(),
+            fame: clan.fame,
+            repair_points: clan.repair_points,
+        })
+        .collect()
+}
+
+/// Returns each current member's participation in the race, by most fame.
+fn get_participation(race: &CurrentRiverRace, members: &[ClanMember]) -> Vec<MemberParticipation> {
+    let mut participation: Vec<_> = members
+        .iter()
+        .map(|member| MemberParticipation {
+            name: member.name.clone(),
+            tag: member.tag.clone(),
+            participation: find_participant(race, member).cloned(),
+        })
+        .collect();
+    participation
+        .sort_by_key(|member| Reverse(member.participation.as_ref().map_or(0, |p| p.fame)));
+
+    participation
+}
+
+/// Returns the current members who haven't used all of today's war decks, or
+/// `None` if there are none to use on a training day.
+fn get_unused_decks(race: &CurrentRiverRace, members: &[ClanMember]) -> Option<Vec<UnusedDecks>> {
+    if !race.is_war_day() {
+        return None;
+    }
+
+    Some(
+        members
+            .iter()
+            .filter_map(|member| {
+                let used = find_participant(race, member).map_or(0, |p| p.decks_used_today);
+                (used < DECKS_PER_DAY).then(|| UnusedDecks {
+                    name: member.name.clone(),
+                    used,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Returns the labels of the past races, oldest first, and each current
+/// member's fame in them.
+fn get_fame_history(
+    clan_tag: &str,
+    log: &[RiverRaceLogEntry],
+    members: &[ClanMember],
+) -> (Vec<String>, Vec<FameHistory>) {
+    let races: Vec<_> = log.iter().rev().collect();
+    let labels = races
+        .iter()
+        .map(|race| format!("S{}W{}", race.season_id, race.section_index + 1))
+        .collect();
+
+    let history = members
+        .iter()
+        .map(|member| {
+            // The member's fame in each race, or `None` if they weren't in it.
+            let fames: Vec<Option<u32>> = races
+                .iter()
+                .map(|race| {
+                    race.standing(clan_tag)?
+                        .clan
+                        .participants
+                        .iter()
+                        .find(|participant| participant.tag == member.tag)
+                        .map(|participant| participant.fame)
+                })
+                .collect();
+
+            let participated: Vec<u32> = fames.iter().flatten().copied().collect();
+            let average = if participated.is_empty() {
+                0.
+            } else {
+                participated.iter().sum::<u32>() as f64 / participated.len() as f64
+            };
+
+            FameHistory {
+                name: member.name.clone(),
+                fames,
+                average,
+            }
+        })
+        .collect();
+
+    (labels, history)
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap, env::var, fs::read_to_string, path::PathBuf, str::FromStr, sync::OnceLock,
+};
+
+use crate::{api::tokens::TokenRotation, util::BAD_JSON_ERR_MSG};
+
+/// The profile used if no other is chosen.
+const DEFAULT_PROFILE: &str = "default";
+
+/// The profile in use, set once at startup.
+static PROFILE: OnceLock<Profile> = OnceLock::new();
+
+/// The config file, from `~/.config/parse-royale/config.toml` by default,
+/// e.g.:
+/// ```toml
+/// default_profile = "main"
+///
+/// [profiles.main]
+/// token = "..."
+/// tokens = ["...", "..."]
+/// token_file = "/run/secrets/clash_tokens"
+/// token_command = "pass show clash-royale"
+/// token_rotation = "failover"
+/// player = "QQUUCL"
+/// format = "json"
+/// cache_ttl = 120
+/// archetype_rules = "archetypes.toml"
+/// base_url = "https://proxy.royaleapi.dev/v1"
+///
+/// [profiles.main.rosters]
+/// clanmates = ["QQUUCL", "2PP"]
+/// ```
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    /// The profile used if none is chosen with `--profile`.
+    default_profile: Option<String>,
+
+    /// The profiles by name.
+    profiles: HashMap<String, Profile>,
+}
+
+/// A set of defaults, overridden by CLI flags.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    /// The developer token, used over `TOKEN` in `.env` or the environment.
+    pub token: Option<String>,
+
+    /// More developer tokens, e.g. for other IPs, used along with `token`.
+    pub tokens: Vec<String>,
+
+    /// A file with more developer tokens, one per line.
+    pub token_file: Option<String>,
+
+    /// A shell command that prints more developer tokens, one per line (e.g.
+    /// from a secret manager).
+    pub token_command: Option<String>,
+
+    /// How requests are spread over the developer tokens.
+    pub token_rotation: Option<TokenRotation>,
+
+    /// The player tag `-p` uses if none is given.
+    pub player: Option<String>,
+
+    /// How reports are printed.
+    pub format: Option<OutputFormat>,
+
+    /// The seconds `serve` and `metrics` cache API responses for.
+    pub cache_ttl: Option<u64>,
+
+    /// The API's base URL, e.g. of a proxy.
+    pub base_url: Option<String>,
+
+    /// The archetype rules file used where no `--rules` is given.
+    pub archetype_rules: Option<String>,
+
+    /// Named lists of tags, given as `@NAME` wherever tags are listed.
+    pub rosters: HashMap<String, Vec<String>>,
+}
+
+/// How reports are printed.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    /// Indented text.
+    #[default]
+    Text,
+
+    /// Pretty JSON, for reports that have a structured form.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown output format `{s}` (`text` or `json`)")),
+        }
+    }
+}
+
+impl Profile {
+    /// Reads the named profile (or the file's default) from the config file at
+    /// the path, or the default path if none is given.
+    /// Returns an empty profile if there's no file at the default path, or an
+    /// error if the file is invalid or doesn't have the named profile.
+    pub fn load(path: Option<&str>, name: Option<&str>) -> Result<Self, String> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => match default_path() {
+                Some(path) if path.exists() => path,
+                _ if name.is_none() => return Ok(Self::default()),
+                _ => return Err("No config file to choose a profile from".to_string()),
+            },
+        };
+
+        let s = read_to_string(&path)
+            .map_err(|_| format!("Failed to read config `{}`", path.display()))?;
+        let mut config: ConfigFile = toml::from_str(&s).map_err(|e| {
+            format!(
+                "Invalid config `{}` {}",
+                path.display(),
+                describe_toml_error(&s, &e)
+            )
+        })?;
+
+        let chosen = name.or(config.default_profile.as_deref());
+        match config.profiles.remove(chosen.unwrap_or(DEFAULT_PROFILE)) {
+            Some(profile) => Ok(profile),
+            None if chosen.is_none() => Ok(Self::default()),
+            None => Err(format!(
+                "No profile `{}` in config `{}`",
+                chosen.unwrap_or_default(),
+                path.display()
+            )),
+        }
+    }
+
+    /// Returns whether the profile gives any developer tokens.
+    pub fn has_tokens(&self) -> bool {
+        self.token.is_some()
+            || !self.tokens.is_empty()
+            || self.token_file.is_some()
+            || self.token_command.is_some()
+    }
+
+    /// Returns the tags, with any `@NAME` replaced by the roster's tags.
+    /// Returns an error if a roster doesn't exist.
+    pub fn expand_tags(&self, tags: &[String]) -> Result<Vec<String>, String> {
+        let mut expanded = Vec::new();
+        for tag in tags {
+            match tag.strip_prefix('@') {
+                Some(name) => expanded.extend(
+                    self.rosters
+                        .get(name)
+                        .ok_or_else(|| format!("No roster `{name}` in config"))?
+                        .iter()
+                        .cloned(),
+                ),
+                None => expanded.push(tag.clone()),
+            }
+        }
+
+        Ok(expanded)
+    }
+}
+
+/// Sets the profile in use for the rest of the program.
+/// Returns an error if it's already set.
+pub fn init(profile: Profile) -> Result<(), String> {
+    PROFILE
+        .set(profile)
+        .map_err(|_| "The config profile is already set".to_string())
+}
+
+/// Returns the profile in use, or an empty one if none was set.
+pub fn profile() -> &'static Profile {
+    PROFILE.get_or_init(Profile::default)
+}
+
+/// Returns the report in the profile's output format: JSON, or text from
+/// `describe`.
+pub fn render<T: Serialize>(report: &T, describe: impl Fn(&T) -> String) -> Result<String, String> {
+    match profile().format.unwrap_or_default() {
+        OutputFormat::Text => Ok(describe(report)),
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(report).map_err(|_| BAD_JSON_ERR_MSG.to_string())
+        }
+    }
+}
+
+/// Returns an error if the profile's output format is JSON, for commands that
+/// only print text.
+pub fn require_text(command: &str) -> Result<(), String> {
+    match profile().format.unwrap_or_default() {
+        OutputFormat::Text => Ok(()),
+        OutputFormat::Json => Err(format!("`{command}` can't print JSON, only text")),
+    }
+}
+
+/// Returns the TOML error's line and message (e.g. `(line 3): invalid string`).
+/// Only the message is used, since the full error quotes the line, which could
+/// hold a token or webhook URL.
+pub(crate) fn describe_toml_error(s: &str, e: &toml::de::Error) -> String {
+    let line = e
+        .span()
+        .map_or(0, |span| s[..span.start].lines().count().max(1));
+
+    format!("(line {line}): {}", e.message())
+}
+
+/// Returns the default config file's path
+/// (`$XDG_CONFIG_HOME/parse-royale/config.toml`, falling back to `~/.config`),
+/// or `None` if neither is known.
+fn default_path() -> Option<PathBuf> {
+    let dir = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok()?;
+
+    Some(dir.join("parse-royale").join("config.toml"))
+}
@@ -1,8 +1,10 @@
+mod battle_data;
 mod card_data;
 mod player_data;
 
 use std::env::Args;
 
+use battle_data::get_write_battle_data;
 use card_data::get_write_card_data;
 use player_data::get_write_player_data;
 
@@ -15,6 +17,9 @@ pub fn process_data_commands(mut args: Args) -> Result<(), String> {
             // Data of all cards.
             "--cards" => get_write_card_data(&mut args)?,
 
+            // Player's recent battles.
+            "--battles" => get_write_battle_data(&mut args)?,
+
             _ => return Err("Expected argument (use \"--help\" for a list of commands".to_string()),
         };
 
@@ -0,0 +1,16 @@
+use std::env::Args;
+
+use crate::{
+    api::{api_call, endpoints},
+    util::write_json,
+};
+
+/// Gets the player's recent battles and writes them to a file, e.g. as a
+/// snapshot for `matchups`.
+pub fn get_write_battle_data(args: &mut Args) -> Result<String, String> {
+    let id = args.next().ok_or("Expected player ID and output file")?;
+    let path = args.next().ok_or("Expected output file")?;
+    let json = api_call(&endpoints::battle_log(&id))?;
+
+    write_json(&json, &path).map(|_| format!("- Wrote player #{id}'s battles to `{path}`"))
+}
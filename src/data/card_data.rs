@@ -1,14 +1,14 @@
 use std::env::Args;
 
-use crate::{api::api_call, util::write_json};
-
-/// The URL to get the Clash Royale API's card data.
-const API_CARDS_URL: &str = "https://api.clashroyale.com/v1/cards";
+use crate::{
+    api::{api_call, endpoints},
+    util::write_json,
+};
 
 /// Gets the Clash Royale API's card data and writes it to a file.
 pub fn get_write_card_data(args: &mut Args) -> Result<String, String> {
-    let path = args.next().ok_or_else(|| "Expected output file")?;
-    let json = api_call(API_CARDS_URL)?;
+    let path = args.next().ok_or("Expected output file")?;
+    let json = api_call(&endpoints::cards())?;
 
     write_json(&json, &path).map(|_| format!("- Wrote card data to `{path}`"))
 }
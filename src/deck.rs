@@ -0,0 +1,151 @@
+pub mod archetype;
+mod check;
+mod icons;
+mod link;
+mod render;
+mod war;
+
+use std::env::Args;
+
+use crate::{cards::Card, models::players::PlayerCard};
+
+use archetype::process_classify_commands;
+use check::process_check_commands;
+use link::process_link_commands;
+use render::process_render_commands;
+use war::process_war_commands;
+
+/// The number of cards in a deck.
+pub const DECK_SIZE: usize = 8;
+
+/// The number of slots at the start of a deck whose cards are played evolved,
+/// if their evolution is unlocked.
+pub const EVOLUTION_SLOTS: usize = 2;
+
+/// The start of a deck's copy link, before its card IDs.
+const LINK_PREFIX: &str = "https://link.clashroyale.com/en/?clashroyale://copyDeck?deck=";
+
+/// A deck of 8 different cards.
+/// The first `EVOLUTION_SLOTS` cards are in the evolution slots.
+#[derive(Debug, Clone)]
+pub struct Deck {
+    /// The deck's cards, in order.
+    pub cards: Vec<&'static Card>,
+}
+
+impl Deck {
+    /// Returns a deck of the given cards, or an error if there aren't 8 of
+    /// them or any are repeated.
+    pub fn new(cards: Vec<&'static Card>) -> Result<Self, String> {
+        if cards.len() != DECK_SIZE {
+            return Err(format!(
+                "Expected a deck of {DECK_SIZE} cards, got {}",
+                cards.len()
+            ));
+        }
+        if let Some(card) = cards
+            .iter()
+            .enumerate()
+            .find(|&(i, card)| cards[..i].iter().any(|other| other.id == card.id))
+            .map(|(_, card)| card)
+        {
+            return Err(format!("\"{}\" is in the deck more than once", card.name));
+        }
+
+        Ok(Self { cards })
+    }
+
+    /// Parses a deck from either a copy link or a list of kebab-case card names
+    /// separated by commas or spaces.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if s.contains("deck=") {
+            Self::from_link(s)
+        } else {
+            Self::from_names(s)
+        }
+    }
+
+    /// Parses a deck from a copy link (`...?deck=26000000;26000001;...`).
+    /// Returns an error if the link has no deck or any card ID is unknown.
+    pub fn from_link(link: &str) -> Result<Self, String> {
+        let (_, ids) = link
+            .split_once("deck=")
+            .ok_or_else(|| format!("No deck in link `{link}`"))?;
+        let ids = ids.split('&').next().unwrap_or_default();
+
+        ids.replace("%3B", ";")
+            .replace("%3b", ";")
+            .split(';')
+            .map(|id| {
+                let id = id
+                    .parse()
+                    .map_err(|_| format!("Invalid card ID `{id}` in deck link"))?;
+                Card::from_id(id).ok_or_else(|| format!("Unknown card ID {id} in deck link"))
+            })
+            .collect::<Result<_, _>>()
+            .and_then(Self::new)
+    }
+
+    /// Parses a deck from kebab-case card names separated by commas or spaces.
+    pub fn from_names(names: &str) -> Result<Self, String> {
+        names
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| !name.is_empty())
+            .map(Card::from_name)
+            .collect::<Result<_, _>>()
+            .and_then(Self::new)
+    }
+
+    /// Returns the deck of the player's cards (e.g. their current deck).
+    /// Returns an error if any card is unknown.
+    pub fn from_player_cards(cards: &[PlayerCard]) -> Result<Self, String> {
+        cards
+            .iter()
+            .map(|card| {
+                Card::from_id(card.id).ok_or_else(|| format!("Unknown card \"{}\"", card.name))
+            })
+            .collect::<Result<_, _>>()
+            .and_then(Self::new)
+    }
+
+    /// Returns the deck's shareable copy link.
+    pub fn to_link(&self) -> String {
+        let ids: Vec<_> = self.cards.iter().map(|card| card.id.to_string()).collect();
+
+        format!("{LINK_PREFIX}{}", ids.join(";"))
+    }
+
+    /// Returns the names of the deck's cards, separated by commas.
+    pub fn names(&self) -> String {
+        let names: Vec<_> = self.cards.iter().map(|card| card.name).collect();
+
+        names.join(", ")
+    }
+}
+
+/// Parses the input arguments after `deck` and prints the output, or returns
+/// an error if any are encountered.
+pub fn process_deck_commands(mut args: Args) -> Result<(), String> {
+    match args.next().as_deref() {
+        // Draw a deck to an image.
+        Some("render") => process_render_commands(args),
+
+        // Convert a deck to its copy link and card names.
+        Some("link") => process_link_commands(args),
+
+        // Check whether a player can run a deck.
+        Some("check") => process_check_commands(args),
+
+        // Pick a player's best decks for the river race.
+        Some("war") => process_war_commands(args),
+
+        // Label decks with their archetypes.
+        Some("classify") => process_classify_commands(args),
+
+        // Errors:
+        Some(s) => Err(format!("Unexpected input: `{s}`")),
+        None => Err(
+            "Expected deck command (`render`, `link`, `check`, `war` or `classify`)".to_string(),
+        ),
+    }
+}
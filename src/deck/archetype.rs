@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize, Serializer};
+use std::{env::Args, fs::read_to_string};
+
+use crate::{
+    api::{api_get, endpoints},
+    cards::Card,
+    config,
+    models::{battles::Battle, cards::CardCatalog, players::PlayerCard},
+    player::Player,
+};
+
+use super::Deck;
+
+/// The default archetype rules.
+const DEFAULT_RULES: &str = include_str!("archetypes.toml");
+
+/// The rules decks are classified by, as written in a rules file.
+#[derive(Deserialize)]
+struct RulesFile {
+    win_conditions: Vec<String>,
+    spells: Vec<String>,
+    archetypes: Vec<ArchetypeRuleFile>,
+}
+
+/// An archetype's rule, as written in a rules file.
+#[derive(Deserialize)]
+struct ArchetypeRuleFile {
+    name: String,
+    #[serde(default)]
+    cards: Vec<String>,
+    min_cards: Option<usize>,
+    min_average_elixir: Option<f64>,
+    max_average_elixir: Option<f64>,
+}
+
+/// The rules decks are classified by.
+pub struct ArchetypeRules {
+    /// Cards that win games by damaging towers.
+    win_conditions: Vec<&'static Card>,
+
+    /// Spells.
+    spells: Vec<&'static Card>,
+
+    /// The archetypes, in order of priority.
+    archetypes: Vec<ArchetypeRule>,
+}
+
+/// The rule a deck must match to be an archetype.
+struct ArchetypeRule {
+    /// The archetype's name.
+    name: String,
+
+    /// The cards the deck needs some of.
+    cards: Vec<&'static Card>,
+
+    /// How many of `cards` the deck needs.
+    min_cards: usize,
+
+    /// The lowest average elixir the deck can have.
+    min_average_elixir: Option<f64>,
+
+    /// The highest average elixir the deck can have.
+    max_average_elixir: Option<f64>,
+}
+
+/// A deck's archetype, win conditions and spells.
+#[derive(Serialize)]
+pub struct Classification {
+    /// The deck's archetype, or `None` if it matches no rule.
+    pub archetype: Option<String>,
+
+    /// The deck's win conditions.
+    #[serde(serialize_with = "serialize_names")]
+    pub win_conditions: Vec<&'static Card>,
+
+    /// The deck's spells.
+    #[serde(serialize_with = "serialize_names")]
+    pub spells: Vec<&'static Card>,
+
+    /// The deck's average elixir cost, if every card's cost is known.
+    pub average_elixir: Option<f64>,
+}
+
+impl ArchetypeRules {
+    /// Parses the rules from a TOML string, or returns an error if it's
+    /// invalid or names an unknown card.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let file: RulesFile =
+            toml::from_str(s).map_err(|e| format!("Invalid archetype rules: {e}"))?;
+        let cards = |names: &[String]| -> Result<Vec<&'static Card>, String> {
+            names.iter().map(|name| Card::from_name(name)).collect()
+        };
+
+        Ok(Self {
+            win_conditions: cards(&file.win_conditions)?,
+            spells: cards(&file.spells)?,
+            archetypes: file
+                .archetypes
+                .into_iter()
+                .map(|rule| {
+                    Ok(ArchetypeRule {
+                        cards: cards(&rule.cards)?,
+                        min_cards: rule.min_cards.unwrap_or(1),
+                        name: rule.name,
+                        min_average_elixir: rule.min_average_elixir,
+                        max_average_elixir: rule.max_average_elixir,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+        })
+    }
+
+    /// Reads the rules from the file at the given path, or the config profile's
+    /// `archetype_rules` if there's no path, or the default rules if neither is
+    /// given.
+    pub fn load(path: Option<&str>) -> Result<Self, String> {
+        match path.or(config::profile().archetype_rules.as_deref()) {
+            Some(path) => {
+                let s = read_to_string(path)
+                    .map_err(|_| format!("Failed to read archetype rules `{path}`"))?;
+                Self::parse(&s)
+            }
+            None => Self::parse(DEFAULT_RULES),
+        }
+    }
+
+    /// Classifies the deck. `elixir_costs` are the cards' elixir costs in the
+    /// deck's order, if known; rules on average elixir are skipped without
+    /// them.
+    pub fn classify(&self, deck: &Deck, elixir_costs: Option<&[u32]>) -> Classification {
+        let in_deck = |cards: &[&'static Card]| -> Vec<&'static Card> {
+            deck.cards
+                .iter()
+                .copied()
+                .filter(|card| cards.iter().any(|other| other.id == card.id))
+                .collect()
+        };
+        let average_elixir = elixir_costs
+            .filter(|costs| !costs.is_empty())
+            .map(|costs| costs.iter().sum::<u32>() as f64 / costs.len() as f64);
+
+        let archetype = self
+            .archetypes
+            .iter()
+            .find(|rule| {
+                let has_cards =
+                    rule.cards.is_empty() || in_deck(&rule.cards).len() >= rule.min_cards;
+                let min_ok = rule
+                    .min_average_elixir
+                    .is_none_or(|min| average_elixir.is_none_or(|average| average >= min));
+                let max_ok = rule
+                    .max_average_elixir
+                    .is_none_or(|max| average_elixir.is_none_or(|average| average <= max));
+
+                has_cards && min_ok && max_ok
+            })
+            .map(|rule| rule.name.clone());
+
+        Classification {
+            archetype,
+            win_conditions: in_deck(&self.win_conditions),
+            spells: in_deck(&self.spells),
+            average_elixir,
+        }
+    }
+
+    /// Classifies a deck of the player's cards (e.g. their current deck or a
+    /// deck from a battle), which have their elixir costs.
+    pub fn classify_player_cards(&self, cards: &[PlayerCard]) -> Result<Classification, String> {
+        let deck = Deck::from_player_cards(cards)?;
+        let costs: Option<Vec<u32>> = cards.iter().map(|card| card.elixir_cost).collect();
+
+        Ok(self.classify(&deck, costs.as_deref()))
+    }
+}
+
+impl Classification {
+    /// Returns a string describing the classification.
+    /// The string is formatted to be pushed onto a command's `output`.
+    pub fn describe(&self) -> String {
+        let names = |cards: &[&'static Card]| -> String {
+            if cards.is_empty() {
+                "None".to_string()
+            } else {
+                cards
+                    .iter()
+                    .map(|card| card.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        };
+
+        let mut output = format!(
+            "\n\tArchetype: {}",
+            self.archetype.as_deref().unwrap_or("Unknown")
+        );
+        output.push_str(&format!(
+            "\n\tWin Conditions: {}",
+            names(&self.win_conditions)
+        ));
+        output.push_str(&format!(
+            "\n\tSpells ({}): {}",
+            self.spells.len(),
+            names(&self.spells)
+        ));
+        if let Some(average) = self.average_elixir {
+            output.push_str(&format!("\n\tAverage Elixir: {average:.1}"));
+        }
+
+        output
+    }
+}
+
+/// The deck(s) to classify.
+enum Target {
+    /// A given deck.
+    Deck(Deck),
+
+    /// A player's current deck, by their ID.
+    Player(String),
+
+    /// The decks in a player's recent battles, by their ID.
+    Battles(String),
+}
+
+/// Parses the input arguments after `deck classify` and prints the decks'
+/// classifications, or returns an error if any are encountered.
+///
+/// The deck is either given directly, or is a `--player`'s current deck, or is
+/// each deck in a player's recent `--battles`.
+pub fn process_classify_commands(mut args: Args) -> Result<(), String> {
+    let mut rules_path = None;
+    let mut target = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => rules_path = Some(args.next().ok_or("Expected rules file")?),
+            "--player" => target = Some(Target::Player(args.next().ok_or("Expected player ID")?)),
+            "--battles" => target = Some(Target::Battles(args.next().ok_or("Expected player ID")?)),
+            _ if target.is_none() => target = Some(Target::Deck(Deck::parse(&arg)?)),
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    config::require_text("deck classify")?;
+    let rules = ArchetypeRules::load(rules_path.as_deref())?;
+    let output = match target.ok_or("Expected deck, `--player` or `--battles`")? {
+        Target::Deck(deck) => {
+            let catalog: CardCatalog = api_get(&endpoints::cards())?;
+            let costs: Option<Vec<u32>> = deck
+                .cards
+                .iter()
+                .map(|card| catalog.get(card.id).and_then(|card| card.elixir_cost))
+                .collect();
+            let classification = rules.classify(&deck, costs.as_deref());
+
+            format!("- Deck: {}{}", deck.names(), classification.describe())
+        }
+        Target::Player(id) => {
+            let player = Player::new(&id)?;
+            let classification = rules.classify_player_cards(&player.current_deck()?)?;
+
+            format!(
+                "- \"{}\" Current Deck:{}",
+                player.username(),
+                classification.describe()
+            )
+        }
+        Target::Battles(id) => get_battles_info(&rules, &id)?,
+    };
+
+    println!("{output}");
+
+    Ok(())
+}
+
+/// Gets the player's recent battles and returns a string containing the
+/// archetypes of both sides' decks.
+fn get_battles_info(rules: &ArchetypeRules, id: &str) -> Result<String, String> {
+    let battles: Vec<Battle> = api_get(&endpoints::battle_log(id))?;
+    let mut output = format!("- #{} Battle Decks:", id.trim_start_matches('#'));
+
+    let archetype = |cards: &[PlayerCard]| -> String {
+        rules
+            .classify_player_cards(cards)
+            .ok()
+            .and_then(|classification| classification.archetype)
+            .unwrap_or_else(|| "Unknown".to_string())
+    };
+
+    for battle in &battles {
+        // Only 1v1 battles have a single deck per side.
+        let (Some(team), Some(opponent)) = (battle.team.first(), battle.opponent.first()) else {
+            continue;
+        };
+        let result = match battle.won() {
+            Some(true) => "Win",
+            Some(false) => "Loss",
+            None => "Draw",
+        };
+
+        output.push_str(&format!(
+            "\n\t{}: {} vs \"{}\" {} ({result})",
+            battle.battle_time,
+            archetype(&team.cards),
+            opponent.name,
+            archetype(&opponent.cards)
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Serializes the cards as their names.
+fn serialize_names<S: Serializer>(
+    cards: &[&'static Card],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(cards.iter().map(|card| card.name))
+}
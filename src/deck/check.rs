@@ -0,0 +1,178 @@
+use serde::Serialize;
+use std::env::Args;
+
+use crate::{
+    config::render,
+    models::players::PlayerCard,
+    player::Player,
+    upgrades::{UpgradeCost, MAX_UPGRADE_LEVEL},
+    util::next_number,
+};
+
+use super::{Deck, EVOLUTION_SLOTS};
+
+/// A player's levels and upgrade costs for a deck's cards.
+#[derive(Serialize)]
+pub struct DeckCheck {
+    /// The player's username.
+    pub player: String,
+
+    /// The level upgrade costs are to.
+    pub target: i64,
+
+    /// The deck's cards, in order.
+    pub cards: Vec<CardCheck>,
+
+    /// The average level of the unlocked cards, or `None` if there are none.
+    pub average_level: Option<f64>,
+
+    /// The cards the player hasn't unlocked.
+    pub missing: Vec<&'static str>,
+
+    /// The cards and gold needed to upgrade every unlocked card to the target.
+    pub total: UpgradeCost,
+}
+
+/// A player's level and upgrade cost for one of a deck's cards.
+#[derive(Serialize)]
+pub struct CardCheck {
+    /// The card's name.
+    pub name: &'static str,
+
+    /// The card's level, or `None` if the player hasn't unlocked it.
+    pub level: Option<i64>,
+
+    /// Whether the card is in an evolution slot.
+    pub evolution_slot: bool,
+
+    /// Whether the player has unlocked the card's evolution, or `None` if it
+    /// has none or the card isn't unlocked.
+    pub evolution_unlocked: Option<bool>,
+
+    /// The cards and gold needed to upgrade it to the target, or `None` if it
+    /// isn't unlocked.
+    pub cost: Option<UpgradeCost>,
+}
+
+/// Parses the input arguments after `deck check` and prints whether the player
+/// can run the deck, or returns an error if any are encountered.
+pub fn process_check_commands(mut args: Args) -> Result<(), String> {
+    let id = args.next().ok_or("Expected player ID and deck")?;
+    let deck = Deck::parse(&args.next().ok_or("Expected deck link or card names")?)?;
+    let mut target = MAX_UPGRADE_LEVEL;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The level to get the upgrade costs to.
+            "--level" => target = next_number(&mut args, "target level")?,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let player = Player::new(&id)?;
+    let check = DeckCheck::new(&player, &deck, target)?;
+    println!("{}", render(&check, DeckCheck::describe)?);
+
+    Ok(())
+}
+
+impl DeckCheck {
+    /// Checks each of the deck's cards' levels and upgrade costs in the
+    /// player's collection, or returns an error if the data is invalid.
+    pub fn new(player: &Player, deck: &Deck, target: i64) -> Result<Self, String> {
+        let collection = player.cards()?;
+        let mut cards = Vec::new();
+        let mut levels = Vec::new();
+        let mut total = UpgradeCost::default();
+        let mut missing = Vec::new();
+
+        for (i, card) in deck.cards.iter().enumerate() {
+            let mut check = CardCheck {
+                name: card.name,
+                level: None,
+                evolution_slot: i < EVOLUTION_SLOTS,
+                evolution_unlocked: None,
+                cost: None,
+            };
+
+            if let Some(owned) = collection.iter().find(|owned| owned.id == card.id) {
+                let level = owned.normalized_level();
+                let cost = owned.rarity()?.upgrade_cost(level, target, owned.count)?;
+                levels.push(level);
+                total.cards += cost.cards;
+                total.gold += cost.gold;
+
+                check.level = Some(level);
+                check.evolution_unlocked = get_evolution_unlocked(owned);
+                check.cost = Some(cost);
+            } else {
+                missing.push(card.name);
+            }
+
+            cards.push(check);
+        }
+
+        Ok(Self {
+            player: player.username().to_string(),
+            target,
+            cards,
+            average_level: (!levels.is_empty())
+                .then(|| levels.iter().sum::<i64>() as f64 / levels.len() as f64),
+            missing,
+            total,
+        })
+    }
+
+    /// Returns the check as the CLI prints it.
+    pub fn describe(&self) -> String {
+        let mut output = format!(
+            "- \"{}\" Deck Check (target level {}):",
+            self.player, self.target
+        );
+
+        for card in &self.cards {
+            let Some(level) = card.level else {
+                output.push_str(&format!("\n\t{}: Not unlocked", card.name));
+                continue;
+            };
+
+            output.push_str(&format!("\n\t{}: Level {level}", card.name));
+            if card.evolution_slot {
+                output.push_str(match card.evolution_unlocked {
+                    None => " (evo slot, has no evolution)",
+                    Some(true) => " (evo slot, evolution unlocked)",
+                    Some(false) => " (evo slot, evolution not unlocked)",
+                });
+            }
+            if let Some(cost) = card.cost.filter(|cost| cost.gold > 0) {
+                output.push_str(&format!(
+                    ", needs {} cards and {} gold",
+                    cost.cards, cost.gold
+                ));
+            }
+        }
+
+        if let Some(average) = self.average_level {
+            output.push_str(&format!("\n\tAverage Level: {average:.2}"));
+        }
+        if self.missing.is_empty() {
+            output.push_str("\n\tOwns every card");
+        } else {
+            output.push_str(&format!("\n\tMissing cards: {}", self.missing.join(", ")));
+        }
+        output.push_str(&format!(
+            "\n\tTo level {}: {} cards, {} gold",
+            self.target, self.total.cards, self.total.gold
+        ));
+
+        output
+    }
+}
+
+/// Returns whether the player has unlocked the card's evolution, or `None` if
+/// it has none.
+fn get_evolution_unlocked(card: &PlayerCard) -> Option<bool> {
+    card.max_evolution_level.map(|_| card.has_evolution())
+}
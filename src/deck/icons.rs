@@ -0,0 +1,88 @@
+use image::{load_from_memory, open, DynamicImage};
+use reqwest::blocking::get;
+use std::{
+    fs::{create_dir_all, write},
+    path::PathBuf,
+};
+
+/// The directory card icons are cached in by default.
+pub const DEFAULT_ICON_CACHE_DIR: &str = ".parse-royale/icons";
+
+/// A local directory of card icons, named `{id}.png` (or `{id}-evo.png` for
+/// evolutions).
+pub struct IconCache {
+    /// The cache's directory.
+    dir: PathBuf,
+
+    /// Whether missing icons may be downloaded.
+    download: bool,
+}
+
+impl IconCache {
+    /// Returns a cache in the given directory.
+    /// If `download` is not set, only icons already in the directory are used.
+    pub fn new(dir: impl Into<PathBuf>, download: bool) -> Self {
+        Self {
+            dir: dir.into(),
+            download,
+        }
+    }
+
+    /// Returns the icon of the card with the given ID, downloading it from the
+    /// URL into the cache if it isn't there.
+    /// Returns an error if it isn't cached and can't be downloaded.
+    pub fn get(&self, id: i64, evolved: bool, url: Option<&str>) -> Result<DynamicImage, String> {
+        let name = if evolved {
+            format!("{id}-evo.png")
+        } else {
+            format!("{id}.png")
+        };
+        let path = self.dir.join(name);
+
+        if path.exists() {
+            return open(&path).map_err(|_| format!("Failed to read icon `{}`", path.display()));
+        }
+
+        let url = url
+            .filter(|_| self.download)
+            .ok_or_else(|| format!("Icon `{}` is not cached", path.display()))?;
+        let bytes = get(url)
+            .and_then(|response| response.error_for_status())
+            .and_then(|response| response.bytes())
+            .map_err(|_| format!("Failed to download icon `{url}`"))?;
+
+        create_dir_all(&self.dir)
+            .and_then(|_| write(&path, &bytes))
+            .map_err(|_| format!("Failed to write icon `{}`", path.display()))?;
+        load_from_memory(&bytes).map_err(|_| format!("Got a bad icon from `{url}`"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The checked-in card icons.
+    const FIXTURE_ICONS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/icons");
+
+    #[test]
+    fn gets_cached_icons() {
+        let icons = IconCache::new(FIXTURE_ICONS, false);
+
+        let icon = icons.get(26000000, false, None).unwrap();
+        assert_eq!((icon.width(), icon.height()), (30, 36));
+        let evolution = icons.get(26000000, true, None).unwrap();
+        assert_ne!(icon.to_rgba8(), evolution.to_rgba8());
+    }
+
+    #[test]
+    fn missing_icon_is_an_error_offline() {
+        let icons = IconCache::new(FIXTURE_ICONS, false);
+
+        // Not downloaded, even with a URL.
+        let error = icons
+            .get(1, false, Some("http://127.0.0.1:9/1.png"))
+            .unwrap_err();
+        assert!(error.ends_with("1.png` is not cached"), "{error}");
+    }
+}
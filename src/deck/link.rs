@@ -0,0 +1,17 @@
+use std::env::Args;
+
+use super::Deck;
+
+/// Parses the input arguments after `deck link` and prints the deck's cards
+/// and copy link, or returns an error if any are encountered.
+pub fn process_link_commands(mut args: Args) -> Result<(), String> {
+    let deck = Deck::parse(&args.next().ok_or("Expected deck link or card names")?)?;
+
+    if let Some(arg) = args.next() {
+        return Err(format!("Unexpected input: `{arg}`"));
+    }
+
+    println!("- Deck: {}\n\tLink: {}", deck.names(), deck.to_link());
+
+    Ok(())
+}
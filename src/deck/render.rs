@@ -0,0 +1,357 @@
+use image::{
+    imageops::{overlay, resize, FilterType},
+    Rgba, RgbaImage,
+};
+use std::env::Args;
+
+use crate::{
+    api::{api_get, endpoints},
+    models::{cards::CardCatalog, players::PlayerCard},
+    player::Player,
+};
+
+use super::{
+    icons::{IconCache, DEFAULT_ICON_CACHE_DIR},
+    Deck, DECK_SIZE, EVOLUTION_SLOTS,
+};
+
+/// The size of each card's cell in the image, in pixels.
+const CELL_WIDTH: u32 = 150;
+const CELL_HEIGHT: u32 = 180;
+
+/// The space around and between cells, in pixels.
+const GAP: u32 = 8;
+
+/// The number of cards in each row of the image.
+const COLUMNS: u32 = 4;
+
+/// The height of the level banner at the bottom of each cell, in pixels.
+const BANNER_HEIGHT: u32 = 32;
+
+/// The size each pixel of the level digits is drawn at.
+const DIGIT_SCALE: u32 = 4;
+
+/// The image's background colour.
+const BACKGROUND: Rgba<u8> = Rgba([24, 28, 40, 255]);
+
+/// The level banner's colour.
+const BANNER: Rgba<u8> = Rgba([0, 0, 0, 180]);
+
+/// The level digits' colour.
+const TEXT: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+/// The colour of the border drawn around evolved cards.
+const EVOLUTION_BORDER: Rgba<u8> = Rgba([190, 90, 255, 255]);
+
+/// The 3x5 pixel glyphs of the digits 0-9, one row per byte (lowest 3 bits).
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// A card to draw in a deck image.
+pub struct RenderCard {
+    /// The card's in-game ID.
+    pub id: i64,
+
+    /// The card's in-game level, or `None` to leave it out.
+    pub level: Option<i64>,
+
+    /// Whether the card is drawn evolved.
+    pub evolved: bool,
+
+    /// The URL to download the card's icon from if it isn't cached.
+    pub icon_url: Option<String>,
+}
+
+/// Parses the input arguments after `deck render`, draws the deck and writes
+/// it to a PNG file, or returns an error if any are encountered.
+///
+/// The deck is either given with `--deck` or is the `--player`'s current deck.
+/// If a player is given, their card levels are drawn.
+pub fn process_render_commands(mut args: Args) -> Result<(), String> {
+    let path = args.next().ok_or("Expected output file")?;
+    let mut player_id = None;
+    let mut deck = None;
+    let mut cache_dir = DEFAULT_ICON_CACHE_DIR.to_string();
+    let mut download = true;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The deck and whose cards it uses.
+            "--player" => player_id = Some(args.next().ok_or("Expected player ID")?),
+            "--deck" => {
+                let s = args.next().ok_or("Expected deck link or card names")?;
+                deck = Some(Deck::parse(&s)?);
+            }
+
+            // Where icons are cached.
+            "--cache" => cache_dir = args.next().ok_or("Expected icon cache directory")?,
+
+            // Only use icons that are already cached.
+            "--offline" => download = false,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let player = player_id.as_deref().map(Player::new).transpose()?;
+    let deck = match (deck, &player) {
+        (Some(deck), _) => deck,
+        (None, Some(player)) => Deck::from_player_cards(&player.current_deck()?)?,
+        (None, None) => return Err("Expected `--player` or `--deck`".to_string()),
+    };
+    let collection = player
+        .as_ref()
+        .map(|player| player.cards())
+        .transpose()?
+        .unwrap_or_default();
+
+    // Without a player, the catalog gives the icon URLs and which cards have
+    // evolutions.
+    let catalog = if player.is_none() && download {
+        Some(api_get::<CardCatalog>(&endpoints::cards())?)
+    } else {
+        None
+    };
+
+    let cards = get_render_cards(&deck, &collection, catalog.as_ref());
+    let image = render_deck(&cards, &IconCache::new(cache_dir, download))?;
+    image
+        .save(&path)
+        .map_err(|_| format!("Failed to write image `{path}`"))?;
+
+    println!("- Wrote deck to `{path}`\n\tLink: {}", deck.to_link());
+
+    Ok(())
+}
+
+/// Returns the deck's cards to draw.
+/// Levels and evolutions come from the player's collection if it's given (it's
+/// empty otherwise), and icon URLs from it or the catalog.
+fn get_render_cards(
+    deck: &Deck,
+    collection: &[PlayerCard],
+    catalog: Option<&CardCatalog>,
+) -> Vec<RenderCard> {
+    deck.cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| {
+            let owned = collection.iter().find(|owned| owned.id == card.id);
+            let icon_urls = owned
+                .map(|owned| &owned.icon_urls)
+                .or_else(|| catalog.and_then(|catalog| card.icon_urls(catalog)));
+            let has_evolution = match owned {
+                Some(owned) => owned.has_evolution(),
+                None => catalog
+                    .and_then(|catalog| catalog.get(card.id))
+                    .is_some_and(|card| card.max_evolution_level.is_some()),
+            };
+            let evolved = i < EVOLUTION_SLOTS && has_evolution;
+            let icon_url = icon_urls.and_then(|urls| {
+                if evolved {
+                    urls.evolution_medium.clone()
+                } else {
+                    urls.medium.clone()
+                }
+            });
+
+            RenderCard {
+                id: card.id,
+                level: owned.map(PlayerCard::normalized_level),
+                evolved,
+                icon_url,
+            }
+        })
+        .collect()
+}
+
+/// Draws the deck's cards in a grid with their levels, using icons from the
+/// cache. Returns an error if the deck isn't 8 cards or an icon can't be
+/// loaded.
+pub fn render_deck(cards: &[RenderCard], icons: &IconCache) -> Result<RgbaImage, String> {
+    if cards.len() != DECK_SIZE {
+        return Err(format!(
+            "Expected a deck of {DECK_SIZE} cards, got {}",
+            cards.len()
+        ));
+    }
+
+    let rows = (DECK_SIZE as u32).div_ceil(COLUMNS);
+    let width = COLUMNS * (CELL_WIDTH + GAP) + GAP;
+    let height = rows * (CELL_HEIGHT + GAP) + GAP;
+    let mut image = RgbaImage::from_pixel(width, height, BACKGROUND);
+
+    for (i, card) in cards.iter().enumerate() {
+        let x = GAP + (i as u32 % COLUMNS) * (CELL_WIDTH + GAP);
+        let y = GAP + (i as u32 / COLUMNS) * (CELL_HEIGHT + GAP);
+
+        if card.evolved {
+            fill_rect(
+                &mut image,
+                x - GAP / 2,
+                y - GAP / 2,
+                CELL_WIDTH + GAP,
+                CELL_HEIGHT + GAP,
+                EVOLUTION_BORDER,
+            );
+        }
+
+        let icon = icons.get(card.id, card.evolved, card.icon_url.as_deref())?;
+        let icon = resize(
+            &icon.to_rgba8(),
+            CELL_WIDTH,
+            CELL_HEIGHT,
+            FilterType::Triangle,
+        );
+        overlay(&mut image, &icon, x.into(), y.into());
+
+        if let Some(level) = card.level {
+            let banner_y = y + CELL_HEIGHT - BANNER_HEIGHT;
+            fill_rect(&mut image, x, banner_y, CELL_WIDTH, BANNER_HEIGHT, BANNER);
+            draw_number(
+                &mut image,
+                level,
+                x + CELL_WIDTH / 2,
+                banner_y + BANNER_HEIGHT / 2,
+            );
+        }
+    }
+
+    Ok(image)
+}
+
+/// Blends a rectangle of the colour onto the image.
+fn fill_rect(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, colour: Rgba<u8>) {
+    let alpha = colour[3] as u32;
+
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            let pixel = image.get_pixel_mut(px, py);
+            for c in 0..3 {
+                pixel[c] =
+                    ((colour[c] as u32 * alpha + pixel[c] as u32 * (255 - alpha)) / 255) as u8;
+            }
+            pixel[3] = pixel[3].max(colour[3]);
+        }
+    }
+}
+
+/// Draws the number's digits centered on the given point.
+fn draw_number(image: &mut RgbaImage, number: i64, center_x: u32, center_y: u32) {
+    let digits: Vec<usize> = number
+        .to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| d as usize)
+        .collect();
+
+    // Each digit is 3 pixels wide with a 1 pixel space after it.
+    let width = (digits.len() as u32 * 4 - 1) * DIGIT_SCALE;
+    let height = 5 * DIGIT_SCALE;
+    let left = center_x.saturating_sub(width / 2);
+    let top = center_y.saturating_sub(height / 2);
+
+    for (i, &digit) in digits.iter().enumerate() {
+        for (row, bits) in DIGITS[digit].iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) != 0 {
+                    let x = left + (i as u32 * 4 + col) * DIGIT_SCALE;
+                    let y = top + row as u32 * DIGIT_SCALE;
+                    fill_rect(image, x, y, DIGIT_SCALE, DIGIT_SCALE, TEXT);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{load_from_memory, ImageFormat};
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// The checked-in icons of `DECK`'s cards, each a solid colour.
+    const FIXTURE_ICONS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/icons");
+
+    /// A deck whose icons are all in `FIXTURE_ICONS`.
+    const DECK: &str = "knight,musketeer,hog-rider,skeletons,ice-spirit,cannon,fireball,the-log";
+
+    /// Returns the fixture deck's cards to draw, without levels or evolutions.
+    fn fixture_cards() -> Vec<RenderCard> {
+        get_render_cards(&Deck::parse(DECK).unwrap(), &[], None)
+    }
+
+    /// Returns the top left corner of the card's cell.
+    fn cell(i: u32) -> (u32, u32) {
+        (
+            GAP + (i % COLUMNS) * (CELL_WIDTH + GAP),
+            GAP + (i / COLUMNS) * (CELL_HEIGHT + GAP),
+        )
+    }
+
+    #[test]
+    fn renders_fixture_deck_to_png() {
+        let mut cards = fixture_cards();
+        cards[0].evolved = true;
+        cards[1].level = Some(14);
+
+        let icons = IconCache::new(FIXTURE_ICONS, false);
+        let image = render_deck(&cards, &icons).unwrap();
+
+        let mut png = Cursor::new(Vec::new());
+        image.write_to(&mut png, ImageFormat::Png).unwrap();
+        let png = load_from_memory(png.get_ref()).unwrap();
+        assert_eq!(png.width(), COLUMNS * (CELL_WIDTH + GAP) + GAP);
+        assert_eq!(png.height(), 2 * (CELL_HEIGHT + GAP) + GAP);
+
+        // Each tile is filled with its card's icon.
+        for (i, card) in cards.iter().enumerate() {
+            let (x, y) = cell(i as u32);
+            let centre = *image.get_pixel(x + CELL_WIDTH / 2, y + CELL_HEIGHT / 3);
+            let icon = icons.get(card.id, card.evolved, None).unwrap().to_rgba8();
+            assert_ne!(centre, BACKGROUND, "tile {i} is empty");
+            assert_eq!(centre, *icon.get_pixel(0, 0), "tile {i} has the wrong icon");
+        }
+
+        // The evolved card has a border and the levelled one a banner.
+        let (x, y) = cell(0);
+        assert_eq!(*image.get_pixel(x - GAP / 2, y), EVOLUTION_BORDER);
+        let (x, y) = cell(1);
+        assert_ne!(
+            *image.get_pixel(x + 1, y + CELL_HEIGHT - 1),
+            *image.get_pixel(x + 1, y + 1)
+        );
+    }
+
+    #[test]
+    fn missing_icon_is_an_error() {
+        let mut cards = fixture_cards();
+        cards[2].evolved = true;
+
+        let error = render_deck(&cards, &IconCache::new(FIXTURE_ICONS, false)).unwrap_err();
+        assert!(error.contains("26000021-evo.png"), "{error}");
+        assert!(error.contains("is not cached"), "{error}");
+    }
+
+    #[test]
+    fn bad_deck_is_an_error() {
+        let mut cards = fixture_cards();
+        cards.pop();
+
+        let error = render_deck(&cards, &IconCache::new(FIXTURE_ICONS, false)).unwrap_err();
+        assert_eq!(error, "Expected a deck of 8 cards, got 7");
+        assert!(Deck::parse("knight,not-a-card").is_err());
+    }
+}
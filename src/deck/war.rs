@@ -0,0 +1,651 @@
+use serde::Serialize;
+use std::{env::Args, fs::read_to_string};
+
+use crate::{
+    cards::{Card, NUM_CARDS},
+    config::render,
+    models::players::PlayerCard,
+    player::Player,
+    upgrades::MAX_UPGRADE_LEVEL,
+};
+
+use super::Deck;
+
+/// The number of decks used in river races.
+pub const WAR_DECKS: usize = 4;
+
+/// The candidate decks used if no pool file is given, by name.
+const BUILTIN_DECKS: [(&str, &str); 15] = [
+    ("Hog 2.6", "hog-rider musketeer ice-spirit skeletons cannon the-log fireball ice-golem"),
+    ("Log Bait", "goblin-barrel princess goblin-gang knight inferno-tower rocket the-log ice-spirit"),
+    ("Golem Beatdown", "golem night-witch baby-dragon lumberjack tornado lightning mega-minion barbarian-barrel"),
+    ("X-Bow 3.0", "x-bow tesla archers knight ice-spirit skeletons fireball the-log"),
+    ("LavaLoon", "lava-hound balloon mega-minion minions tombstone fireball zap guards"),
+    ("P.E.K.K.A Bridge Spam", "pekka battle-ram bandit royal-ghost electro-wizard magic-archer poison zap"),
+    ("Royal Giant Fisherman", "royal-giant fisherman hunter mother-witch electro-spirit skeleton-dragons earthquake giant-snowball"),
+    ("Graveyard Freeze", "graveyard freeze ice-wizard baby-dragon tornado barbarian-barrel valkyrie tombstone"),
+    ("Miner Wall Breakers", "miner poison wall-breakers bomb-tower spear-goblins electro-spirit the-log bats"),
+    ("Royal Hogs Recruits", "royal-hogs royal-recruits flying-machine zappies goblin-cage fireball arrows barbarian-barrel"),
+    ("Sparky Goblin Giant", "sparky goblin-giant rage zap mega-minion dark-prince electro-wizard heal-spirit"),
+    ("Three Musketeers", "three-musketeers battle-ram elixir-collector bandit royal-ghost ice-golem minion-horde zap"),
+    ("Mortar Bait", "mortar miner skeleton-barrel goblin-gang cannon-cart dart-goblin royal-delivery arrows"),
+    ("Giant Double Prince", "giant prince dark-prince mega-minion electro-wizard furnace fireball zap"),
+    ("Goblin Drill", "goblin-drill bomb-tower wall-breakers valkyrie skeletons tesla fireball the-log"),
+];
+
+/// What the chosen decks maximize.
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Metric {
+    /// The average level of each deck's cards.
+    #[serde(rename = "level")]
+    AverageLevel,
+
+    /// The lowest level of each deck's cards.
+    MinLevel,
+
+    /// The least gold needed to max out each deck's cards.
+    Gold,
+}
+
+/// A candidate deck the player can run.
+struct Candidate {
+    /// The deck's name.
+    name: String,
+
+    /// The deck.
+    deck: Deck,
+
+    /// The deck's cards.
+    cards: CardSet,
+
+    /// The deck's score by the chosen metric; higher is better.
+    score: f64,
+}
+
+/// The decks chosen for a player's river race.
+#[derive(Serialize)]
+struct WarDecksReport {
+    /// The player's username.
+    player: String,
+
+    /// What the decks maximize.
+    metric: Metric,
+
+    /// The chosen decks, best first.
+    decks: Vec<WarDeck>,
+
+    /// The better-scoring decks that were left out.
+    trade_offs: Vec<TradeOff>,
+
+    /// The decks in the pool the player can't run.
+    unplayable: Vec<Unplayable>,
+}
+
+/// A deck chosen for the river race.
+#[derive(Serialize)]
+struct WarDeck {
+    /// The deck's name.
+    name: String,
+
+    /// The names of the deck's cards, in order.
+    cards: Vec<&'static str>,
+
+    /// The deck's copy link.
+    link: String,
+
+    /// The deck's score by the metric; higher is better, so gold is negative.
+    score: f64,
+}
+
+/// A better-scoring deck left out for sharing cards with chosen ones.
+#[derive(Serialize)]
+struct TradeOff {
+    /// The deck's name.
+    name: String,
+
+    /// The deck's score by the metric.
+    score: f64,
+
+    /// The chosen decks it shares cards with.
+    conflicts: Vec<Conflict>,
+}
+
+/// The cards a left out deck shares with a chosen one.
+#[derive(Serialize)]
+struct Conflict {
+    /// The chosen deck's name.
+    deck: String,
+
+    /// The names of the shared cards.
+    shared: Vec<&'static str>,
+}
+
+/// A deck in the pool the player can't run.
+#[derive(Serialize)]
+struct Unplayable {
+    /// The deck's name.
+    name: String,
+
+    /// The names of the cards the player hasn't unlocked.
+    missing: Vec<&'static str>,
+}
+
+impl WarDecksReport {
+    /// Returns the report as the CLI prints it.
+    fn describe(&self) -> String {
+        let mut output = format!(
+            "- \"{}\" War Decks (by {}):",
+            self.player,
+            get_metric_name(self.metric)
+        );
+        for (i, deck) in self.decks.iter().enumerate() {
+            output.push_str(&format!(
+                "\n\t{}. \"{}\" ({}): {}\n\t\tLink: {}",
+                i + 1,
+                deck.name,
+                format_score(deck.score, self.metric),
+                deck.cards.join(", "),
+                deck.link
+            ));
+        }
+
+        if !self.trade_offs.is_empty() {
+            output.push_str("\n\tTrade-offs:");
+        }
+        for trade_off in &self.trade_offs {
+            let conflicts: Vec<_> = trade_off
+                .conflicts
+                .iter()
+                .map(|conflict| {
+                    format!("{} with \"{}\"", conflict.shared.join(", "), conflict.deck)
+                })
+                .collect();
+            output.push_str(&format!(
+                "\n\t\t\"{}\" ({}) left out, shares {}",
+                trade_off.name,
+                format_score(trade_off.score, self.metric),
+                conflicts.join("; ")
+            ));
+        }
+
+        if !self.unplayable.is_empty() {
+            let unplayable: Vec<_> = self
+                .unplayable
+                .iter()
+                .map(|deck| format!("\"{}\" ({})", deck.name, deck.missing.join(", ")))
+                .collect();
+            output.push_str(&format!(
+                "\n\tUnplayable (missing cards): {}",
+                unplayable.join(", ")
+            ));
+        }
+
+        output
+    }
+}
+
+/// Parses the input arguments after `deck war`, picks the best decks for the
+/// river race and prints them, or returns an error if any are encountered.
+pub fn process_war_commands(mut args: Args) -> Result<(), String> {
+    let id = args.next().ok_or("Expected player ID")?;
+    let mut pool_path = None;
+    let mut metric = Metric::AverageLevel;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // A file of candidate decks, one link or list of card names per line.
+            "--pool" => pool_path = Some(args.next().ok_or("Expected pool file")?),
+
+            // What to maximize.
+            "--metric" => {
+                metric = match args.next().as_deref() {
+                    Some("level") => Metric::AverageLevel,
+                    Some("min-level") => Metric::MinLevel,
+                    Some("gold") => Metric::Gold,
+                    _ => return Err("Expected `level`, `min-level` or `gold`".to_string()),
+                }
+            }
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let pool = match pool_path {
+        Some(path) => read_pool(&path)?,
+        None => builtin_pool()?,
+    };
+
+    let player = Player::new(&id)?;
+    let (candidates, unplayable) = get_candidates(pool, &player.cards()?, metric)?;
+    let chosen = choose_decks(&candidates)?;
+
+    let report = WarDecksReport {
+        player: player.username().to_string(),
+        metric,
+        decks: chosen
+            .iter()
+            .map(|&i| {
+                let candidate = &candidates[i];
+                WarDeck {
+                    name: candidate.name.clone(),
+                    cards: candidate.deck.cards.iter().map(|card| card.name).collect(),
+                    link: candidate.deck.to_link(),
+                    score: candidate.score,
+                }
+            })
+            .collect(),
+        trade_offs: get_trade_offs(&candidates, &chosen),
+        unplayable,
+    };
+
+    println!("{}", render(&report, WarDecksReport::describe)?);
+
+    Ok(())
+}
+
+/// Reads candidate decks from the file, one link or list of card names per
+/// line. Blank lines and lines starting with `#` are skipped.
+/// Returns an error if the file can't be read or a deck is invalid.
+fn read_pool(path: &str) -> Result<Vec<(String, Deck)>, String> {
+    let s = read_to_string(path).map_err(|_| format!("Failed to read pool file `{path}`"))?;
+
+    s.lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            Deck::parse(line)
+                .map(|deck| (format!("Line {}", i + 1), deck))
+                .map_err(|e| format!("{e} (`{path}` line {})", i + 1))
+        })
+        .collect()
+}
+
+/// Returns the built-in candidate decks.
+fn builtin_pool() -> Result<Vec<(String, Deck)>, String> {
+    BUILTIN_DECKS
+        .iter()
+        .map(|&(name, cards)| Deck::from_names(cards).map(|deck| (name.to_string(), deck)))
+        .collect()
+}
+
+/// Splits the pool into the decks the player can run, scored by the metric
+/// and sorted best first, and the ones they can't.
+/// Returns an error if the collection's data is invalid.
+fn get_candidates(
+    pool: Vec<(String, Deck)>,
+    collection: &[PlayerCard],
+    metric: Metric,
+) -> Result<(Vec<Candidate>, Vec<Unplayable>), String> {
+    let mut candidates = Vec::new();
+    let mut unplayable = Vec::new();
+    for (name, deck) in pool {
+        let missing: Vec<_> = deck
+            .cards
+            .iter()
+            .filter(|card| !collection.iter().any(|owned| owned.id == card.id))
+            .map(|card| card.name)
+            .collect();
+
+        if missing.is_empty() {
+            let score = score_deck(&deck, collection, metric)?;
+            let cards = card_set(&deck);
+            candidates.push(Candidate {
+                name,
+                deck,
+                cards,
+                score,
+            });
+        } else {
+            unplayable.push(Unplayable { name, missing });
+        }
+    }
+
+    // Try the best decks first so good sets are found early.
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    Ok((candidates, unplayable))
+}
+
+/// Returns the indices of the best `WAR_DECKS` candidates without shared
+/// cards, or an error if there are none.
+/// The candidates must be sorted by score, highest first.
+fn choose_decks(candidates: &[Candidate]) -> Result<Vec<usize>, String> {
+    find_best_set(candidates).ok_or_else(|| {
+        format!("No {WAR_DECKS} decks in the pool without shared cards that the player can run")
+    })
+}
+
+/// A set of cards, as a bit set of their indices in `Card::ALL`, so sets can
+/// be compared quickly while searching.
+#[derive(Clone, Copy, Default)]
+struct CardSet([u64; NUM_CARDS.div_ceil(64)]);
+
+impl CardSet {
+    /// Adds the card at the index in `Card::ALL`.
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    /// Returns whether the sets have no cards in common.
+    fn is_disjoint(&self, other: &Self) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a & b == 0)
+    }
+
+    /// Returns the cards in either set.
+    fn union(mut self, other: &Self) -> Self {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a |= b;
+        }
+        self
+    }
+}
+
+/// Returns the deck's cards as a set.
+fn card_set(deck: &Deck) -> CardSet {
+    let mut set = CardSet::default();
+    for i in deck
+        .cards
+        .iter()
+        .filter_map(|card| Card::ALL.iter().position(|other| other.id == card.id))
+    {
+        set.insert(i);
+    }
+
+    set
+}
+
+/// Scores the deck by the metric, or returns an error if the collection's data
+/// is invalid. Every card in the deck must be in the collection.
+fn score_deck(deck: &Deck, collection: &[PlayerCard], metric: Metric) -> Result<f64, String> {
+    let owned: Vec<_> = deck
+        .cards
+        .iter()
+        .filter_map(|card| collection.iter().find(|owned| owned.id == card.id))
+        .collect();
+    let levels = owned.iter().map(|card| card.normalized_level());
+
+    Ok(match metric {
+        Metric::AverageLevel => levels.sum::<i64>() as f64 / owned.len() as f64,
+        Metric::MinLevel => levels.min().unwrap_or(0) as f64,
+        Metric::Gold => {
+            let mut gold = 0;
+            for card in &owned {
+                gold += card
+                    .rarity()?
+                    .upgrade_cost(card.normalized_level(), MAX_UPGRADE_LEVEL, card.count)?
+                    .gold;
+            }
+            -(gold as f64)
+        }
+    })
+}
+
+/// Returns the indices of the `WAR_DECKS` candidates without shared cards that
+/// have the highest total score, or `None` if there are no such candidates.
+/// The candidates must be sorted by score, highest first.
+fn find_best_set(candidates: &[Candidate]) -> Option<Vec<usize>> {
+    let mut best = None;
+    let mut chosen = Vec::with_capacity(WAR_DECKS);
+    search(
+        candidates,
+        0,
+        CardSet::default(),
+        0.,
+        &mut chosen,
+        &mut best,
+    );
+
+    best.map(|(_, set)| set)
+}
+
+/// Searches for the best set of candidates from `start` on, keeping the best
+/// found so far in `best`.
+fn search(
+    candidates: &[Candidate],
+    start: usize,
+    used: CardSet,
+    score: f64,
+    chosen: &mut Vec<usize>,
+    best: &mut Option<(f64, Vec<usize>)>,
+) {
+    if chosen.len() == WAR_DECKS {
+        if best.as_ref().is_none_or(|(best, _)| score > *best) {
+            *best = Some((score, chosen.clone()));
+        }
+        return;
+    }
+
+    let needed = WAR_DECKS - chosen.len();
+    for i in start..candidates.len() {
+        // Candidates are sorted, so this one is the best left and no set from
+        // here can beat the best.
+        let bound = score + candidates[i].score * needed as f64;
+        if best.as_ref().is_some_and(|(best, _)| bound <= *best) {
+            return;
+        }
+
+        let candidate = &candidates[i];
+        if used.is_disjoint(&candidate.cards) {
+            chosen.push(i);
+            search(
+                candidates,
+                i + 1,
+                used.union(&candidate.cards),
+                score + candidate.score,
+                chosen,
+                best,
+            );
+            chosen.pop();
+        }
+    }
+}
+
+/// Returns the better-scoring candidates that weren't chosen, with the chosen
+/// decks they share cards with.
+fn get_trade_offs(candidates: &[Candidate], chosen: &[usize]) -> Vec<TradeOff> {
+    let worst_chosen = chosen
+        .iter()
+        .map(|&i| candidates[i].score)
+        .fold(f64::INFINITY, f64::min);
+
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|&(i, candidate)| !chosen.contains(&i) && candidate.score > worst_chosen)
+        .map(|(_, candidate)| TradeOff {
+            name: candidate.name.clone(),
+            score: candidate.score,
+            conflicts: chosen
+                .iter()
+                .map(|&j| &candidates[j])
+                .filter(|other| !other.cards.is_disjoint(&candidate.cards))
+                .map(|other| Conflict {
+                    deck: other.name.clone(),
+                    shared: candidate
+                        .deck
+                        .cards
+                        .iter()
+                        .filter(|card| other.deck.cards.iter().any(|c| c.id == card.id))
+                        .map(|card| card.name)
+                        .collect(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Returns the metric's name.
+fn get_metric_name(metric: Metric) -> &'static str {
+    match metric {
+        Metric::AverageLevel => "average level",
+        Metric::MinLevel => "lowest level",
+        Metric::Gold => "gold to max",
+    }
+}
+
+/// Returns the score formatted for the metric.
+fn format_score(score: f64, metric: Metric) -> String {
+    match metric {
+        Metric::AverageLevel => format!("average level {score:.2}"),
+        Metric::MinLevel => format!("lowest level {score:.0}"),
+        Metric::Gold => format!("{:.0} gold to max", -score),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a common card at the in-game level, with no copies collected.
+    fn owned(card: &Card, level: i64) -> PlayerCard {
+        PlayerCard {
+            name: card.name.to_string(),
+            id: card.id,
+            rarity: "common".to_string(),
+            level,
+            max_level: 14,
+            evolution_level: None,
+            max_evolution_level: None,
+            star_level: None,
+            elixir_cost: None,
+            count: 0,
+            icon_urls: Default::default(),
+        }
+    }
+
+    /// Returns every card in the built-in pool at level 11, with the given
+    /// decks' cards at level 14.
+    fn collection(maxed: &[&str]) -> Vec<PlayerCard> {
+        let pool = builtin_pool().unwrap();
+        let mut cards: Vec<PlayerCard> = Vec::new();
+        for (name, deck) in &pool {
+            for card in &deck.cards {
+                let level = if maxed.contains(&name.as_str()) {
+                    14
+                } else {
+                    11
+                };
+                match cards.iter_mut().find(|owned| owned.id == card.id) {
+                    Some(owned) => owned.level = owned.level.max(level),
+                    None => cards.push(owned(card, level)),
+                }
+            }
+        }
+
+        cards
+    }
+
+    /// Returns the candidates' names.
+    fn names<'a>(candidates: &'a [Candidate], chosen: &[usize]) -> Vec<&'a str> {
+        chosen
+            .iter()
+            .map(|&i| candidates[i].name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn chooses_the_best_decks_without_shared_cards() {
+        let collection = collection(&["Hog 2.6", "X-Bow 3.0"]);
+        let (candidates, unplayable) =
+            get_candidates(builtin_pool().unwrap(), &collection, Metric::AverageLevel).unwrap();
+        assert_eq!(candidates.len(), BUILTIN_DECKS.len());
+        assert!(unplayable.is_empty());
+
+        let chosen = choose_decks(&candidates).unwrap();
+        assert_eq!(chosen.len(), WAR_DECKS);
+        for (i, &a) in chosen.iter().enumerate() {
+            for &b in &chosen[i + 1..] {
+                assert!(candidates[a].cards.is_disjoint(&candidates[b].cards));
+            }
+        }
+        // The maxed decks share cards, so only one of them is chosen.
+        let chosen_names = names(&candidates, &chosen);
+        let maxed = chosen_names
+            .iter()
+            .filter(|&&name| name == "Hog 2.6" || name == "X-Bow 3.0")
+            .count();
+        assert_eq!(maxed, 1, "{chosen_names:?}");
+
+        // No other set of decks without shared cards scores higher.
+        let total = |set: &[usize]| set.iter().map(|&i| candidates[i].score).sum::<f64>();
+        let n = candidates.len();
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        let set = [a, b, c, d];
+                        let disjoint = set.iter().enumerate().all(|(i, &x)| {
+                            set[i + 1..]
+                                .iter()
+                                .all(|&y| candidates[x].cards.is_disjoint(&candidates[y].cards))
+                        });
+                        assert!(!disjoint || total(&set) <= total(&chosen), "{set:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn card_sets_cover_every_card() {
+        let mut first = CardSet::default();
+        let mut last = CardSet::default();
+        first.insert(0);
+        last.insert(NUM_CARDS - 1);
+
+        assert!(first.is_disjoint(&last));
+        let both = first.union(&last);
+        assert!(!both.is_disjoint(&first));
+        assert!(!both.is_disjoint(&last));
+    }
+
+    #[test]
+    fn scores_by_lowest_level() {
+        let deck = Deck::from_names(BUILTIN_DECKS[0].1).unwrap();
+        let mut collection: Vec<_> = deck.cards.iter().map(|card| owned(card, 13)).collect();
+        collection[3].level = 9;
+
+        let score = score_deck(&deck, &collection, Metric::MinLevel).unwrap();
+        assert_eq!(score, 9.);
+        let score = score_deck(&deck, &collection, Metric::AverageLevel).unwrap();
+        assert_eq!(score, 12.5);
+    }
+
+    #[test]
+    fn scores_by_gold_to_max() {
+        let deck = Deck::from_names(BUILTIN_DECKS[0].1).unwrap();
+        let mut collection: Vec<_> = deck.cards.iter().map(|card| owned(card, 13)).collect();
+
+        // Each common card needs 100000 gold from level 13.
+        let score = score_deck(&deck, &collection, Metric::Gold).unwrap();
+        assert_eq!(score, -800000.);
+
+        // Maxed cards need none, and collected copies don't save gold.
+        collection[0].level = 14;
+        collection[1].count = 5000;
+        let score = score_deck(&deck, &collection, Metric::Gold).unwrap();
+        assert_eq!(score, -700000.);
+    }
+
+    #[test]
+    fn decks_sharing_cards_are_an_error() {
+        // Only Hog 2.6 and X-Bow 3.0 are playable, and they share cards.
+        let collection: Vec<_> = builtin_pool()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| name == "Hog 2.6" || name == "X-Bow 3.0")
+            .flat_map(|(_, deck)| deck.cards.iter().map(|card| owned(card, 11)))
+            .collect();
+
+        let (candidates, unplayable) =
+            get_candidates(builtin_pool().unwrap(), &collection, Metric::AverageLevel).unwrap();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(unplayable.len(), BUILTIN_DECKS.len() - 2);
+
+        let e = choose_decks(&candidates).err().unwrap();
+        assert!(e.starts_with("No 4 decks"), "{e}");
+    }
+}
@@ -0,0 +1,223 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::env::Args;
+
+use crate::{
+    api::{api_get, endpoints},
+    config::render,
+    models::{
+        events::{ChallengeGroup, GlobalTournament, Reward},
+        paging::Page,
+        tournaments::GameMode,
+    },
+    util::parse_api_time,
+};
+
+/// The format event times are displayed in.
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+
+/// Whether an event is running.
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    /// The event is running.
+    Active,
+
+    /// The event hasn't started yet.
+    Upcoming,
+
+    /// The event is over.
+    Ended,
+}
+
+/// The global tournaments and challenges that are running or upcoming.
+#[derive(Serialize)]
+struct EventsReport {
+    /// The global tournaments.
+    tournaments: Vec<Scheduled<GlobalTournament>>,
+
+    /// The groups of challenges.
+    challenges: Vec<Scheduled<ChallengeGroup>>,
+}
+
+/// An event with whether it's running.
+#[derive(Serialize)]
+struct Scheduled<T> {
+    /// Whether the event is running.
+    status: Status,
+
+    /// When the event starts.
+    #[serde(skip)]
+    start: DateTime<Utc>,
+
+    /// When the event ends.
+    #[serde(skip)]
+    end: DateTime<Utc>,
+
+    /// The event.
+    #[serde(flatten)]
+    event: T,
+}
+
+impl<T> Scheduled<T> {
+    /// Returns the event with its status at `now`, or an error if its start or
+    /// end time is invalid.
+    fn new(event: T, start: &str, end: &str, now: DateTime<Utc>) -> Result<Self, String> {
+        let start = parse_api_time(start)?;
+        let end = parse_api_time(end)?;
+        let status = if now < start {
+            Status::Upcoming
+        } else if now < end {
+            Status::Active
+        } else {
+            Status::Ended
+        };
+
+        Ok(Self {
+            status,
+            start,
+            end,
+            event,
+        })
+    }
+
+    /// Returns whether the event should be listed, i.e. it hasn't ended and
+    /// has the status asked for, if any.
+    fn is_shown(&self, only: Option<Status>) -> bool {
+        self.status != Status::Ended && only.is_none_or(|only| only == self.status)
+    }
+
+    /// Returns a string containing the event's status and when it runs.
+    fn get_schedule_info(&self) -> String {
+        let status = match self.status {
+            Status::Active => "Active",
+            Status::Upcoming => "Upcoming",
+            Status::Ended => "Ended",
+        };
+
+        format!(
+            "{status}, {} to {}",
+            self.start.format(TIME_FORMAT),
+            self.end.format(TIME_FORMAT)
+        )
+    }
+}
+
+impl EventsReport {
+    /// Returns the events as the CLI prints them.
+    fn describe(&self) -> String {
+        let mut output = "- Global Tournaments:".to_string();
+        for scheduled in &self.tournaments {
+            let tournament = &scheduled.event;
+            output.push_str(&format!(
+                "\n\t\"{}\" ({}): {}",
+                tournament.title,
+                tournament.tag,
+                scheduled.get_schedule_info(),
+            ));
+            output.push_str(&format!(
+                "\n\t\tGame Mode: {}",
+                get_game_mode_name(tournament.game_mode.as_ref())
+            ));
+            if let Some(level) = tournament.tournament_level {
+                output.push_str(&format!(", Level {level} cards"));
+            }
+            if let Some(max_losses) = tournament.max_losses {
+                output.push_str(&format!(", {max_losses} losses"));
+            }
+            if let Some(min_exp_level) = tournament.min_exp_level {
+                output.push_str(&format!(", King Level {min_exp_level}+"));
+            }
+            output.push_str(get_rewards_info(&tournament.milestone_rewards).as_str());
+        }
+
+        output.push_str("\n- Challenges:");
+        for scheduled in &self.challenges {
+            let schedule = scheduled.get_schedule_info();
+            for challenge in &scheduled.event.challenges {
+                output.push_str(&format!("\n\t\"{}\": {schedule}", challenge.name));
+                output.push_str(&format!(
+                    "\n\t\tGame Mode: {}",
+                    get_game_mode_name(challenge.game_mode.as_ref())
+                ));
+                if let Some(max_wins) = challenge.max_wins {
+                    output.push_str(&format!(", {max_wins} wins max"));
+                }
+                if challenge.casual {
+                    output.push_str(", casual");
+                } else if let Some(max_losses) = challenge.max_losses {
+                    output.push_str(&format!(", {max_losses} losses"));
+                }
+                output.push_str(get_rewards_info(&challenge.prizes).as_str());
+            }
+        }
+
+        output
+    }
+}
+
+/// Parses the input arguments after `events` and prints the global tournaments
+/// and challenges, or returns an error if any are encountered.
+pub fn process_events_commands(args: Args) -> Result<(), String> {
+    // Which events to show; `None` shows both active and upcoming ones.
+    let mut only = None;
+
+    for arg in args {
+        match arg.as_str() {
+            "--active" => only = Some(Status::Active),
+            "--upcoming" => only = Some(Status::Upcoming),
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let tournaments: Page<GlobalTournament> = api_get(&endpoints::global_tournaments())?;
+    let challenges: Vec<ChallengeGroup> = api_get(&endpoints::challenges())?;
+    let now = Utc::now();
+
+    let mut report = EventsReport {
+        tournaments: Vec::new(),
+        challenges: Vec::new(),
+    };
+    for tournament in tournaments.items {
+        let (start, end) = (tournament.start_time.clone(), tournament.end_time.clone());
+        let scheduled = Scheduled::new(tournament, &start, &end, now)?;
+        if scheduled.is_shown(only) {
+            report.tournaments.push(scheduled);
+        }
+    }
+    for group in challenges {
+        let (start, end) = (group.start_time.clone(), group.end_time.clone());
+        let scheduled = Scheduled::new(group, &start, &end, now)?;
+        if scheduled.is_shown(only) {
+            report.challenges.push(scheduled);
+        }
+    }
+
+    println!("{}", render(&report, EventsReport::describe)?);
+
+    Ok(())
+}
+
+/// Returns the game mode's name, or `Unknown` if there isn't one.
+fn get_game_mode_name(game_mode: Option<&GameMode>) -> String {
+    game_mode.map_or("Unknown".to_string(), |mode| mode.display_name())
+}
+
+/// Returns a string listing the event's rewards, by wins needed.
+fn get_rewards_info(rewards: &[Reward]) -> String {
+    if rewards.is_empty() {
+        return String::new();
+    }
+
+    let rewards: Vec<_> = rewards
+        .iter()
+        .map(|reward| match reward.wins {
+            Some(wins) => format!("{wins} wins: {}", reward.describe()),
+            None => reward.describe(),
+        })
+        .collect();
+
+    format!("\n\t\tRewards: {}", rewards.join("; "))
+}
@@ -0,0 +1,21 @@
+pub mod api;
+pub mod archive;
+pub mod cards;
+pub mod catalog;
+pub mod clan;
+pub mod config;
+pub mod data;
+pub mod deck;
+pub mod events;
+pub mod logging;
+pub mod matchups;
+pub mod metrics;
+pub mod models;
+pub mod player;
+pub mod rankings;
+pub mod repl;
+pub mod serve;
+pub mod tournament;
+pub mod upgrades;
+pub mod util;
+pub mod watch;
@@ -0,0 +1,89 @@
+use std::{
+    io::{stderr, IsTerminal},
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{filter::Targets, fmt, prelude::*, registry};
+
+/// Whether tags are hidden from logged URLs.
+static REDACT_TAGS: AtomicBool = AtomicBool::new(false);
+
+/// How log lines are printed.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+
+    /// One JSON object per line, for collecting and searching later.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown log format `{s}` (`text` or `json`)")),
+        }
+    }
+}
+
+/// Starts printing logs to stderr: only errors by default, then also warnings
+/// and each API request with `-v`, debug details with `-vv` and everything,
+/// including from dependencies (e.g. connections), with `-vvv`.
+/// Returns an error if logging was already started.
+pub fn init(verbosity: usize, format: LogFormat, redact_tags: bool) -> Result<(), String> {
+    REDACT_TAGS.store(redact_tags, Ordering::Relaxed);
+
+    let level = match verbosity {
+        0 => LevelFilter::ERROR,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let dependencies = if verbosity >= 3 {
+        LevelFilter::DEBUG
+    } else {
+        LevelFilter::OFF
+    };
+    let targets = Targets::new()
+        .with_target(env!("CARGO_CRATE_NAME"), level)
+        .with_default(dependencies);
+
+    let layer = fmt::layer()
+        .with_writer(stderr)
+        .with_ansi(stderr().is_terminal());
+    let layer = match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.json().flatten_event(true).boxed(),
+    };
+
+    registry()
+        .with(layer.with_filter(targets))
+        .try_init()
+        .map_err(|_| "Logging is already started".to_string())
+}
+
+/// Returns the URL for logging, with any tags (`%23...`) replaced by `%23***`
+/// if chosen.
+pub fn log_url(url: &str) -> String {
+    if !REDACT_TAGS.load(Ordering::Relaxed) {
+        return url.to_string();
+    }
+
+    let mut parts = url.split("%23");
+    let mut redacted = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let tag_len = part
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(part.len());
+        redacted.push_str("%23***");
+        redacted.push_str(&part[tag_len..]);
+    }
+
+    redacted
+}
@@ -1,50 +1,215 @@
-mod api;
-mod cards;
-mod data;
-mod player;
-mod util;
+use std::{
+    env::{args, Args},
+    process::exit,
+};
 
-use std::{env::args, process::exit};
-
-use data::process_data_commands;
-use player::process_player_commands;
+use parse_royale::{
+    api::tokens::redact,
+    archive::process_archive_commands,
+    catalog::process_catalog_commands,
+    clan::process_clan_commands,
+    config::{self, Profile},
+    data::process_data_commands,
+    deck::process_deck_commands,
+    events::process_events_commands,
+    logging::{self, LogFormat},
+    matchups::process_matchups_commands,
+    metrics::process_metrics_commands,
+    player::{process_default_player_commands, process_player_commands, REPORT_FLAGS},
+    rankings::process_rankings_commands,
+    repl::process_repl_commands,
+    serve::process_serve_commands,
+    tournament::process_tournament_commands,
+    watch::process_watch_commands,
+};
 
 /// The string printed when `--help` is pased.
-const HELP_STR: &str = "- List of commands:\
-    \n\t`-p`: Get player info:\
-        \n\t\t`parse-royale -p QQUUCL -c berserker -c miner\
+const HELP_STR: &str = "- Global options, before the command (these override the config file):\
+    \n\t`--config FILE`: Read the config from a file other than `~/.config/parse-royale/config.toml`\
+    \n\t`--profile NAME`: Use a profile other than the config's default\
+    \n\t`--token TOKEN`: Use a developer token other than the config's or `.env`'s (repeat for several)\
+    \n\t`--token-file FILE`: Use the developer tokens in a file, one per line\
+    \n\t`--token-command CMD`: Use the developer tokens a shell command prints, one per line\
+    \n\t`--token-rotation round-robin|failover`: Spread requests over the tokens, or use one until it's rejected\
+    \n\t`--format text|json`: Print reports as text or JSON (one player report per run; `deck classify` and `catalog` only print text)\
+    \n\t`--base-url URL`: Use another base URL for the API (e.g. a proxy)\
+    \n\t`-v`, `-vv` or `-vvv`: Log each API request (URL, status, latency, size, cache and retries) to stderr, then more details\
+    \n\t`--log-format text|json`: Print logs as text or one JSON object per line\
+    \n\t`--redact-tags`: Hide player and clan tags in logged URLs\
+    \n- List of commands:\
+    \n\t`-p`: Get player info (the ID can be left out, or `-p` with it, to use the config's player):\
+        \n\t\t`parse-royale -p QQUUCL --card berserker --card miner --chests --deck --collection`\
+    \n\t`repl`: Load a player once and explore them interactively (`help` inside for commands):\
+        \n\t\t`parse-royale repl QQUUCL`\
     \n\t`-d`: Get raw JSON data to write to a file:\
-        \n\t\t`parse-royale -d -c card_data.json`";
+        \n\t\t`parse-royale -d --cards card_data.json --battles QQUUCL battles.json`\
+    \n\t`rankings`: Get a location's player, clan or Path of Legends rankings:\
+        \n\t\t`parse-royale rankings US --clans --all --limit 1000`\
+    \n\t`clan search`: Search for clans:\
+        \n\t\t`parse-royale clan search --location US --min-members 40 --trophies 7000 --sort score`\
+    \n\t`clan war`: Get a clan's river race report:\
+        \n\t\t`parse-royale clan war 9YJUPU9 --races 4`\
+    \n\t`clan audit`: Get members recommended to kick or promote:\
+        \n\t\t`parse-royale clan audit 9YJUPU9 --inactive-days 7 --min-donations 50 --weeks 2`\
+    \n\t`tournament`: Get a tournament's info and standings, or search for tournaments:\
+        \n\t\t`parse-royale tournament 2PP` or `parse-royale tournament search \"My Cup\"`\
+    \n\t`events`: Get active and upcoming global tournaments and challenges:\
+        \n\t\t`parse-royale events --upcoming`\
+    \n\t`catalog check`: Compare the API's cards against the compiled ones:\
+        \n\t\t`parse-royale catalog check`\
+    \n\t`deck render`: Draw a deck (a copy link or card names) to a PNG file:\
+        \n\t\t`parse-royale deck render deck.png --player QQUUCL --deck hog-rider,musketeer,...`\
+    \n\t`deck link`: Get a deck's copy link or card names:\
+        \n\t\t`parse-royale deck link \"hog-rider musketeer ...\"`\
+    \n\t`deck check`: Check a player's levels and upgrade costs for a deck:\
+        \n\t\t`parse-royale deck check QQUUCL hog-rider,musketeer,... --level 14`\
+    \n\t`deck war`: Pick a player's best 4 river race decks without shared cards:\
+        \n\t\t`parse-royale deck war QQUUCL --pool decks.txt --metric level`\
+    \n\t`deck classify`: Get a deck's archetype, win conditions and spells:\
+        \n\t\t`parse-royale deck classify hog-rider,musketeer,...` or `--player QQUUCL` or `--battles QQUUCL`\
+    \n\t`matchups`: Get a player's win rates against opponent cards and archetypes:\
+        \n\t\t`parse-royale matchups QQUUCL --snapshots battles/ --min-games 3`\
+    \n\t`archive fetch`: Store players' recent battles in a local database:\
+        \n\t\t`parse-royale archive fetch QQUUCL 2PP --db battles.db`\
+    \n\t`archive query`: Search the stored battles:\
+        \n\t\t`parse-royale archive query --player QQUUCL --mode pathOfLegend --from 2025-01-01 --to 2025-01-31 --deck hog-rider --opponent 2PP`\
+    \n\t`watch`: Poll players and clans on a schedule, storing snapshots and reporting changes:\
+        \n\t\t`parse-royale watch --config watch.toml` or `--once` or `--test`\
+    \n\t`serve`: Serve reports as a JSON REST API, with API responses cached:\
+        \n\t\t`parse-royale serve --port 8080 --ttl 60 --workers 4` then `GET /players/QQUUCL/info`, `/players/QQUUCL/cards/miner`,\
+        \n\t\t`/players/QQUUCL/collection`, `/players/QQUUCL/chests`, `/players/QQUUCL/battles` or `/clans/9YJUPU9/audit?weeks=4`\
+    \n\t`metrics`: Serve Prometheus metrics for the players and clans in a `watch` config:\
+        \n\t\t`parse-royale metrics --config watch.toml --port 9090` then `GET /metrics`";
 
 fn main() {
     let mut args = args();
     args.next();
 
-    let result = match args.next().as_deref() {
+    let result = process_global_options(&mut args).and_then(|command| match command.as_deref() {
         // Want help.
         Some("-h") => {
             println!("{HELP_STR}");
             Ok(())
         }
 
+        // Want to explore a player interactively.
+        Some("repl") => process_repl_commands(args),
+
         // Want JSON data to a file.
         Some("-d") => process_data_commands(args),
 
         // Want player info.
         Some("-p") => process_player_commands(args),
 
+        // Want clan info.
+        Some("clan") => process_clan_commands(args),
+
+        // Want location rankings.
+        Some("rankings") => process_rankings_commands(args),
+
+        // Want tournament info.
+        Some("tournament") => process_tournament_commands(args),
+
+        // Want global tournaments and challenges.
+        Some("events") => process_events_commands(args),
+
+        // Want to check the compiled cards.
+        Some("catalog") => process_catalog_commands(args),
+
+        // Want deck tools.
+        Some("deck") => process_deck_commands(args),
+
+        // Want matchup statistics.
+        Some("matchups") => process_matchups_commands(args),
+
+        // Want the battle archive.
+        Some("archive") => process_archive_commands(args),
+
+        // Want to watch players and clans.
+        Some("watch") => process_watch_commands(args),
+
+        // Want the REST API server.
+        Some("serve") => process_serve_commands(args),
+
+        // Want the Prometheus exporter.
+        Some("metrics") => process_metrics_commands(args),
+
+        // Want the config's player's info.
+        Some(s) if REPORT_FLAGS.contains(&s) => {
+            process_default_player_commands(s.to_string(), args)
+        }
+
         // Errors.
         Some(s) => Err(format!(
             "Unknown command `{s}`. Run with `-h` for a list of commands"
         )),
         _ => Err("Run with `-h` for a list of commands.".to_string()),
-    };
+    });
 
     // Return 0 on success or 1 on error and print it.
     exit(if let Err(e) = result {
-        eprintln!("{e}");
+        eprintln!("{}", redact(&e));
         1
     } else {
         0
     })
 }
+
+/// Parses the global options before the command and sets the config profile
+/// from them, returning the command, or returns an error if any are
+/// encountered.
+fn process_global_options(args: &mut Args) -> Result<Option<String>, String> {
+    let mut path = None;
+    let mut name = None;
+    let mut overrides = Profile::default();
+    let mut verbosity = 0;
+    let mut log_format = LogFormat::default();
+    let mut redact_tags = false;
+
+    let command = loop {
+        let arg = args.next();
+        match arg.as_deref() {
+            Some("--config") => path = Some(args.next().ok_or("Expected config file")?),
+            Some("--profile") => name = Some(args.next().ok_or("Expected profile name")?),
+            Some("--token") => overrides.tokens.push(args.next().ok_or("Expected token")?),
+            Some("--token-file") => {
+                overrides.token_file = Some(args.next().ok_or("Expected token file")?)
+            }
+            Some("--token-command") => {
+                overrides.token_command = Some(args.next().ok_or("Expected token command")?)
+            }
+            Some("--token-rotation") => {
+                overrides.token_rotation =
+                    Some(args.next().ok_or("Expected token rotation")?.parse()?)
+            }
+            Some("--format") => {
+                overrides.format = Some(args.next().ok_or("Expected output format")?.parse()?)
+            }
+            Some("--base-url") => overrides.base_url = Some(args.next().ok_or("Expected URL")?),
+            Some(v) if v.starts_with("-v") && v[1..].bytes().all(|c| c == b'v') => {
+                verbosity += v.len() - 1
+            }
+            Some("--log-format") => {
+                log_format = args.next().ok_or("Expected log format")?.parse()?
+            }
+            Some("--redact-tags") => redact_tags = true,
+            _ => break arg,
+        }
+    };
+
+    // Flags override the config.
+    let mut profile = Profile::load(path.as_deref(), name.as_deref())?;
+    if overrides.has_tokens() {
+        profile.token = None;
+        profile.tokens = overrides.tokens;
+        profile.token_file = overrides.token_file;
+        profile.token_command = overrides.token_command;
+    }
+    profile.token_rotation = overrides.token_rotation.or(profile.token_rotation);
+    profile.format = overrides.format.or(profile.format);
+    profile.base_url = overrides.base_url.or(profile.base_url);
+    config::init(profile)?;
+    logging::init(verbosity, log_format, redact_tags)?;
+
+    Ok(command)
+}
@@ -0,0 +1,318 @@
+use serde::Serialize;
+use serde_json::from_str;
+use std::{
+    collections::BTreeMap,
+    env::Args,
+    fs::{read_dir, read_to_string},
+    path::Path,
+};
+
+use crate::{
+    api::{api_get, endpoints},
+    config::render,
+    deck::archetype::ArchetypeRules,
+    models::battles::Battle,
+    util::{next_number, BAD_JSON_ERR_MSG},
+};
+
+/// The z-score of the confidence intervals (95%).
+const Z: f64 = 1.96;
+
+/// The fewest games against a card or archetype for it to be listed, if no
+/// number is given.
+const DEFAULT_MIN_GAMES: u32 = 3;
+
+/// The number of worst matchups called out.
+const WORST_MATCHUPS: usize = 5;
+
+/// A player's results against a card or archetype.
+#[derive(Default)]
+struct Record {
+    /// The games won.
+    wins: u32,
+
+    /// The games played.
+    games: u32,
+}
+
+impl Record {
+    /// Returns the win rate, from 0 to 1.
+    fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games as f64
+    }
+
+    /// Returns the bounds of the win rate's Wilson score confidence interval.
+    fn confidence_interval(&self) -> (f64, f64) {
+        let n = self.games as f64;
+        let p = self.win_rate();
+        let denominator = 1. + Z * Z / n;
+        let center = (p + Z * Z / (2. * n)) / denominator;
+        let margin = Z * (p * (1. - p) / n + Z * Z / (4. * n * n)).sqrt() / denominator;
+
+        (center - margin, center + margin)
+    }
+}
+
+/// A player's win rate against a card or archetype.
+#[derive(Serialize)]
+struct Matchup {
+    /// The card or archetype's name.
+    name: String,
+
+    /// The games won.
+    wins: u32,
+
+    /// The games played.
+    games: u32,
+
+    /// The win rate, from 0 to 1.
+    win_rate: f64,
+
+    /// The bounds of the win rate's 95% confidence interval.
+    confidence_interval: (f64, f64),
+}
+
+impl Matchup {
+    /// Returns the named record's matchup.
+    fn new(name: &str, record: &Record) -> Self {
+        Self {
+            name: name.to_string(),
+            wins: record.wins,
+            games: record.games,
+            win_rate: record.win_rate(),
+            confidence_interval: record.confidence_interval(),
+        }
+    }
+}
+
+/// A player's win rates against the opponent cards and archetypes they've
+/// faced.
+#[derive(Serialize)]
+struct MatchupsReport {
+    /// The player's tag, without the leading `#`.
+    player: String,
+
+    /// The 1v1 battles counted.
+    battles: u32,
+
+    /// The fewest games against a card for it to be listed.
+    min_games: u32,
+
+    /// The matchups against each archetype, from the highest win rate to the
+    /// lowest.
+    by_archetype: Vec<Matchup>,
+
+    /// The matchups against each card, from the highest win rate to the
+    /// lowest.
+    by_card: Vec<Matchup>,
+
+    /// The worst matchups, from the worst.
+    worst: Vec<Matchup>,
+}
+
+impl MatchupsReport {
+    /// Returns the matchups as the CLI prints them.
+    fn describe(&self) -> String {
+        let list = |matchups: &[Matchup]| -> String {
+            matchups
+                .iter()
+                .map(|matchup| format!("\n\t\t{}", format_matchup(matchup)))
+                .collect()
+        };
+
+        let mut output = format!("- #{} Matchups ({} battles):", self.player, self.battles);
+        output.push_str("\n\tBy Archetype:");
+        output.push_str(list(&self.by_archetype).as_str());
+        output.push_str(&format!("\n\tBy Card (at least {} games):", self.min_games));
+        output.push_str(list(&self.by_card).as_str());
+        output.push_str("\n\tWorst Matchups:");
+        if self.worst.is_empty() {
+            output.push_str(" None");
+        }
+        output.push_str(list(&self.worst).as_str());
+
+        output
+    }
+}
+
+/// Parses the input arguments after `matchups` and prints the player's win
+/// rates against each opponent card and archetype, or returns an error if any
+/// are encountered.
+pub fn process_matchups_commands(mut args: Args) -> Result<(), String> {
+    let id = args.next().ok_or("Expected player ID")?;
+    let mut snapshots = Vec::new();
+    let mut fetch = true;
+    let mut min_games = DEFAULT_MIN_GAMES;
+    let mut rules_path = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Stored battle logs (files or directories of them) to merge in.
+            "--snapshots" => snapshots.push(args.next().ok_or("Expected snapshot path")?),
+
+            // Only use stored battle logs.
+            "--no-fetch" => fetch = false,
+
+            "--min-games" => min_games = next_number(&mut args, "minimum games")?,
+            "--rules" => rules_path = Some(args.next().ok_or("Expected rules file")?),
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let mut logs = Vec::new();
+    if fetch {
+        logs.push(api_get(&endpoints::battle_log(&id))?);
+    }
+    for path in &snapshots {
+        // Snapshots may be shared, so only keep the player's battles, from
+        // their side.
+        logs.extend(read_snapshots(Path::new(path))?.into_iter().map(|log| {
+            log.into_iter()
+                .filter_map(|battle| battle.oriented(&id))
+                .collect()
+        }));
+    }
+    let battles = merge_battles(logs);
+    let rules = ArchetypeRules::load(rules_path.as_deref())?;
+
+    // Tally the results against each of the opponent's cards and archetype.
+    let mut by_card: BTreeMap<String, Record> = BTreeMap::new();
+    let mut by_archetype: BTreeMap<String, Record> = BTreeMap::new();
+    let mut games = 0;
+    for battle in &battles {
+        // Only 1v1 battles have a single opponent deck.
+        let [opponent] = battle.opponent.as_slice() else {
+            continue;
+        };
+        if opponent.cards.is_empty() {
+            continue;
+        }
+
+        let won = battle.won() == Some(true);
+        let tally = |record: &mut Record| {
+            record.games += 1;
+            record.wins += won as u32;
+        };
+        games += 1;
+
+        for card in &opponent.cards {
+            tally(by_card.entry(card.name.clone()).or_default());
+        }
+        let archetype = rules
+            .classify_player_cards(&opponent.cards)
+            .ok()
+            .and_then(|classification| classification.archetype)
+            .unwrap_or_else(|| "unknown".to_string());
+        tally(by_archetype.entry(archetype).or_default());
+    }
+
+    let report = MatchupsReport {
+        player: id.trim_start_matches('#').to_string(),
+        battles: games,
+        min_games,
+        by_archetype: get_matchups(&by_archetype, 0),
+        by_card: get_matchups(&by_card, min_games),
+        worst: get_worst_matchups(&by_card, &by_archetype, min_games),
+    };
+
+    println!("{}", render(&report, MatchupsReport::describe)?);
+
+    Ok(())
+}
+
+/// Reads the battle logs stored at the path, which is either a file of one or
+/// a directory of `.json` files of them.
+fn read_snapshots(path: &Path) -> Result<Vec<Vec<Battle>>, String> {
+    if !path.is_dir() {
+        let s = read_to_string(path)
+            .map_err(|_| format!("Failed to read snapshot `{}`", path.display()))?;
+        let battles =
+            from_str(&s).map_err(|_| format!("{BAD_JSON_ERR_MSG} in `{}`", path.display()))?;
+        return Ok(vec![battles]);
+    }
+
+    let mut paths: Vec<_> = read_dir(path)
+        .map_err(|_| format!("Failed to read directory `{}`", path.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    let mut logs = Vec::new();
+    for path in paths {
+        logs.extend(read_snapshots(&path)?);
+    }
+
+    Ok(logs)
+}
+
+/// Merges the battle logs, removing battles that are in more than one.
+/// Battles are the same if they happened at the same time.
+/// Returns the battles from oldest to newest.
+pub fn merge_battles(logs: Vec<Vec<Battle>>) -> Vec<Battle> {
+    let mut battles = BTreeMap::new();
+    for battle in logs.into_iter().flatten() {
+        battles.entry(battle.battle_time.clone()).or_insert(battle);
+    }
+
+    battles.into_values().collect()
+}
+
+/// Returns the matchups of the records with at least `min_games` games, from
+/// the highest win rate to the lowest.
+fn get_matchups(records: &BTreeMap<String, Record>, min_games: u32) -> Vec<Matchup> {
+    let mut records: Vec<_> = records
+        .iter()
+        .filter(|(_, record)| record.games >= min_games.max(1))
+        .collect();
+    records.sort_by(|(_, a), (_, b)| b.win_rate().total_cmp(&a.win_rate()));
+
+    records
+        .into_iter()
+        .map(|(name, record)| Matchup::new(name, record))
+        .collect()
+}
+
+/// Returns the cards and archetypes with the worst win rates, judged by the
+/// upper bound of their confidence interval so small samples aren't called out
+/// on bad luck alone.
+fn get_worst_matchups(
+    by_card: &BTreeMap<String, Record>,
+    by_archetype: &BTreeMap<String, Record>,
+    min_games: u32,
+) -> Vec<Matchup> {
+    let mut worst: Vec<_> = by_card
+        .iter()
+        .chain(by_archetype.iter())
+        .filter(|(_, record)| record.games >= min_games.max(1) && record.win_rate() < 0.5)
+        .collect();
+    worst.sort_by(|(_, a), (_, b)| {
+        a.confidence_interval()
+            .1
+            .total_cmp(&b.confidence_interval().1)
+    });
+
+    worst
+        .into_iter()
+        .take(WORST_MATCHUPS)
+        .map(|(name, record)| Matchup::new(name, record))
+        .collect()
+}
+
+/// Returns a string containing the matchup's win rate, sample size and
+/// confidence interval.
+fn format_matchup(matchup: &Matchup) -> String {
+    let (low, high) = matchup.confidence_interval;
+
+    format!(
+        "{}: {:.1}% win rate ({}/{} games), 95% CI {:.1}%-{:.1}%",
+        matchup.name,
+        100. * matchup.win_rate,
+        matchup.wins,
+        matchup.games,
+        100. * low,
+        100. * high
+    )
+}
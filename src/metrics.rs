@@ -0,0 +1,291 @@
+use serde_json::from_value;
+use std::{env::Args, time::Duration};
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{
+    api::{cache::ResponseCache, endpoints, stats::api_stats, tokens::redact},
+    config,
+    models::{
+        clans::ClanMember, paging::Page, players::PlayerProfile, river_race::CurrentRiverRace,
+    },
+    player::{Player, PlayerInfo},
+    util::{next_number, BAD_JSON_ERR_MSG},
+    watch::WatchConfig,
+};
+
+/// The config file of tracked players and clans read if no other is given.
+const DEFAULT_CONFIG_PATH: &str = "watch.toml";
+
+/// The address the exporter listens on if no other is given.
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 9090;
+
+/// The seconds API responses are cached for if no other time is given, so
+/// frequent scrapes don't hit the rate limit.
+const DEFAULT_TTL: u64 = 60;
+
+/// A metric and its samples, in Prometheus' text format.
+struct Family {
+    name: &'static str,
+    help: &'static str,
+
+    /// `gauge` or `counter`.
+    kind: &'static str,
+
+    /// Each sample's labels (e.g. `{tag="#ABC"}`) and value.
+    samples: Vec<(String, f64)>,
+}
+
+impl Family {
+    /// Returns a metric without samples.
+    fn new(name: &'static str, kind: &'static str, help: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            kind,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Adds a sample with the given labels.
+    fn add(&mut self, labels: &[(&str, &str)], value: impl Into<f64>) {
+        let labels: Vec<_> = labels
+            .iter()
+            .map(|(name, value)| format!("{name}=\"{}\"", escape_label(value)))
+            .collect();
+
+        let labels = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", labels.join(","))
+        };
+
+        self.samples.push((labels, value.into()));
+    }
+
+    /// Returns the metric in Prometheus' text format.
+    fn render(&self) -> String {
+        let mut output = format!(
+            "# HELP {} {}\n# TYPE {} {}\n",
+            self.name, self.help, self.name, self.kind
+        );
+        for (labels, value) in &self.samples {
+            output.push_str(&format!("{}{labels} {value}\n", self.name));
+        }
+
+        output
+    }
+}
+
+/// Parses the input arguments after `metrics` and serves the tracked players'
+/// and clans' metrics at `/metrics` until stopped, or returns an error if any
+/// are encountered.
+/// The tracked players and clans are the ones `watch` polls.
+pub fn process_metrics_commands(mut args: Args) -> Result<(), String> {
+    let mut path = DEFAULT_CONFIG_PATH.to_string();
+    let mut host = DEFAULT_HOST.to_string();
+    let mut port = DEFAULT_PORT;
+    let mut ttl = config::profile().cache_ttl.unwrap_or(DEFAULT_TTL);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The config file of tracked players and clans.
+            "--config" => path = args.next().ok_or("Expected config file")?,
+
+            // The address to listen on.
+            "--host" => host = args.next().ok_or("Expected host")?,
+            "--port" => port = next_number(&mut args, "port")?,
+
+            // The seconds to cache API responses for.
+            "--ttl" => ttl = next_number(&mut args, "cache time")?,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let config = WatchConfig::load(&path)?;
+    let server = Server::http((host.as_str(), port))
+        .map_err(|e| format!("Failed to listen on {host}:{port}: {e}"))?;
+    let cache = ResponseCache::new(Duration::from_secs(ttl));
+
+    println!("- Serving metrics on http://{host}:{port}/metrics");
+
+    for request in server.incoming_requests() {
+        let response = if *request.method() == Method::Get && request.url() == "/metrics" {
+            Response::from_string(get_metrics(&config, &cache)).with_header(
+                Header::from_bytes("Content-Type", "text/plain; version=0.0.4").unwrap(),
+            )
+        } else {
+            Response::from_string("Not found").with_status_code(404)
+        };
+
+        if let Err(e) = request.respond(response) {
+            eprintln!("Failed to respond: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the metrics of the tracked players and clans and of the API
+/// requests made, in Prometheus' text format.
+/// Players and clans that can't be fetched are left out, with their errors
+/// counted in `clash_api_errors_total`.
+fn get_metrics(config: &WatchConfig, cache: &ResponseCache) -> String {
+    let mut trophies = Family::new(
+        "clash_player_trophies",
+        "gauge",
+        "The player's current trophies.",
+    );
+    let mut wins = Family::new("clash_player_wins", "gauge", "The player's total wins.");
+    let mut losses = Family::new("clash_player_losses", "gauge", "The player's total losses.");
+    let mut cards = Family::new(
+        "clash_player_cards_unlocked",
+        "gauge",
+        "The number of cards the player has unlocked.",
+    );
+    let mut donations = Family::new(
+        "clash_player_donations",
+        "gauge",
+        "The cards the player has donated this week.",
+    );
+    let mut clan_fame = Family::new(
+        "clash_clan_war_fame",
+        "gauge",
+        "The clan's fame in the current river race.",
+    );
+    let mut member_fame = Family::new(
+        "clash_clan_member_war_fame",
+        "gauge",
+        "The member's fame in the clan's current river race.",
+    );
+    let mut member_donations = Family::new(
+        "clash_clan_member_donations",
+        "gauge",
+        "The cards the member has donated this week.",
+    );
+
+    for tag in &config.players {
+        let result = get_player_metrics(tag, cache).map(|(profile, info)| {
+            let labels = [
+                ("tag", profile.tag.as_str()),
+                ("name", profile.name.as_str()),
+            ];
+            trophies.add(&labels, profile.trophies);
+            wins.add(&labels, info.wins as f64);
+            losses.add(&labels, info.losses as f64);
+            cards.add(&labels, info.cards_unlocked as f64);
+            donations.add(&labels, profile.donations);
+        });
+        if let Err(e) = result {
+            eprintln!(
+                "Failed to get player #{}: {}",
+                tag.trim_start_matches('#'),
+                redact(&e)
+            );
+        }
+    }
+
+    for tag in &config.clans {
+        let result = get_clan_metrics(tag, cache).map(|(members, race)| {
+            let clan = race.clan.tag.as_str();
+            clan_fame.add(&[("clan", clan), ("name", &race.clan.name)], race.clan.fame);
+
+            for member in &members {
+                let labels = [
+                    ("clan", clan),
+                    ("tag", member.tag.as_str()),
+                    ("name", member.name.as_str()),
+                ];
+                member_donations.add(&labels, member.donations);
+
+                let fame = race
+                    .clan
+                    .participants
+                    .iter()
+                    .find(|participant| participant.tag == member.tag)
+                    .map_or(0, |participant| participant.fame);
+                member_fame.add(&labels, fame);
+            }
+        });
+        if let Err(e) = result {
+            eprintln!(
+                "Failed to get clan #{}: {}",
+                tag.trim_start_matches('#'),
+                redact(&e)
+            );
+        }
+    }
+
+    let stats = api_stats();
+    let mut calls = Family::new(
+        "clash_api_calls_total",
+        "counter",
+        "The number of requests made to the Clash Royale API.",
+    );
+    calls.add(&[], stats.calls as f64);
+    let mut cache_hits = Family::new(
+        "clash_api_cache_hits_total",
+        "counter",
+        "The number of requests answered by the response cache.",
+    );
+    cache_hits.add(&[], stats.cache_hits as f64);
+    let mut errors = Family::new(
+        "clash_api_errors_total",
+        "counter",
+        "The number of failed requests to the Clash Royale API, by reason.",
+    );
+    for (reason, count) in &stats.errors {
+        errors.add(&[("reason", reason)], *count as f64);
+    }
+
+    [
+        trophies,
+        wins,
+        losses,
+        cards,
+        donations,
+        clan_fame,
+        member_fame,
+        member_donations,
+        calls,
+        cache_hits,
+        errors,
+    ]
+    .iter()
+    .map(Family::render)
+    .collect()
+}
+
+/// Returns the player's profile and info, from the cache if it's there.
+fn get_player_metrics(
+    tag: &str,
+    cache: &ResponseCache,
+) -> Result<(PlayerProfile, PlayerInfo), String> {
+    let json = cache.call(&endpoints::player(tag))?;
+    let profile = from_value(json.clone()).map_err(|_| BAD_JSON_ERR_MSG)?;
+    let info = PlayerInfo::new(&Player::from_json(tag, json)?)?;
+
+    Ok((profile, info))
+}
+
+/// Returns the clan's members and current river race, from the cache if
+/// they're there.
+fn get_clan_metrics(
+    tag: &str,
+    cache: &ResponseCache,
+) -> Result<(Vec<ClanMember>, CurrentRiverRace), String> {
+    let members = cache.get::<Page<ClanMember>>(&endpoints::clan_members(tag))?;
+    let race = cache.get(&endpoints::current_river_race(tag))?;
+
+    Ok((members.items, race))
+}
+
+/// Escapes the label value's backslashes, quotes and newlines.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,14 @@
+//! Typed models of the Clash Royale API's responses, shared by the blocking
+//! and async clients.
+
+pub mod battles;
+pub mod cards;
+pub mod chests;
+pub mod clans;
+pub mod events;
+pub mod locations;
+pub mod paging;
+pub mod players;
+pub mod rankings;
+pub mod river_race;
+pub mod tournaments;
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use super::{players::PlayerCard, tournaments::GameMode};
+
+/// A battle from a player's battle log, from `/players/{tag}/battlelog`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Battle {
+    /// The kind of battle (e.g. `PvP`, `pathOfLegend`, `riverRacePvP`).
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// When the battle happened, in the API's time format.
+    pub battle_time: String,
+
+    /// The battle's game mode.
+    pub game_mode: Option<GameMode>,
+
+    /// The player and their teammate, if any.
+    pub team: Vec<BattlePlayer>,
+
+    /// The opponents.
+    pub opponent: Vec<BattlePlayer>,
+}
+
+/// A player in a battle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BattlePlayer {
+    /// The player's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The player's username.
+    pub name: String,
+
+    /// The crowns the player took.
+    #[serde(default)]
+    pub crowns: u32,
+
+    /// The trophies the player gained or lost, for trophy battles.
+    pub trophy_change: Option<i32>,
+
+    /// The player's deck.
+    #[serde(default)]
+    pub cards: Vec<PlayerCard>,
+}
+
+impl Battle {
+    /// Returns `Some(true)` if the team won, `Some(false)` if it lost, or
+    /// `None` if it was a draw.
+    pub fn won(&self) -> Option<bool> {
+        let crowns = |players: &[BattlePlayer]| players.iter().map(|p| p.crowns).max().unwrap_or(0);
+        let (team, opponent) = (crowns(&self.team), crowns(&self.opponent));
+
+        (team != opponent).then_some(team > opponent)
+    }
+
+    /// Returns the tags of everyone in the battle, sorted.
+    pub fn participants(&self) -> Vec<&str> {
+        let mut tags: Vec<_> = self
+            .team
+            .iter()
+            .chain(&self.opponent)
+            .map(|player| player.tag.as_str())
+            .collect();
+        tags.sort_unstable();
+
+        tags
+    }
+
+    /// Returns the battle from the perspective of the player with the given
+    /// tag (with them on `team`), or `None` if they weren't in it.
+    pub fn oriented(mut self, tag: &str) -> Option<Self> {
+        let tag = tag.trim_start_matches('#');
+        let is_player = |player: &BattlePlayer| player.tag.trim_start_matches('#') == tag;
+
+        if self.opponent.iter().any(is_player) {
+            std::mem::swap(&mut self.team, &mut self.opponent);
+        }
+
+        self.team.iter().any(is_player).then_some(self)
+    }
+}
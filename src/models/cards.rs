@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cards::Card;
+
+/// The catalog of every card, from `/cards`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardCatalog {
+    /// The cards that can be put in decks.
+    pub items: Vec<CatalogCard>,
+
+    /// The tower troops.
+    #[serde(default)]
+    pub support_items: Vec<CatalogCard>,
+}
+
+/// A card in the `CardCatalog`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogCard {
+    /// The card's name.
+    pub name: String,
+
+    /// The card's in-game ID.
+    pub id: i64,
+
+    /// The card's rarity (`common`, `rare`, `epic`, `legendary` or
+    /// `champion`).
+    pub rarity: String,
+
+    /// The card's max level, relative to its rarity's starting level.
+    pub max_level: u32,
+
+    /// The card's max evolution level, or `None` if it has no evolution.
+    pub max_evolution_level: Option<u32>,
+
+    /// The card's elixir cost, or `None` if it has none (e.g. Mirror).
+    pub elixir_cost: Option<u32>,
+
+    /// The card's icon URLs.
+    #[serde(default)]
+    pub icon_urls: IconUrls,
+}
+
+/// The URLs of a card's icons.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IconUrls {
+    /// The card's icon.
+    pub medium: Option<String>,
+
+    /// The card's evolution icon, if it has an evolution.
+    pub evolution_medium: Option<String>,
+}
+
+/// The differences between the API's `CardCatalog` and the compiled cards.
+pub struct CatalogDiff<'a> {
+    /// Cards in the API but not compiled, e.g. new releases.
+    pub missing: Vec<&'a CatalogCard>,
+
+    /// Cards compiled but not in the API.
+    pub removed: Vec<&'static Card>,
+}
+
+impl CardCatalog {
+    /// Returns the card with the given ID, or `None` if there isn't one.
+    pub fn get(&self, id: i64) -> Option<&CatalogCard> {
+        self.items.iter().find(|card| card.id == id)
+    }
+
+    /// Compares the catalog against the compiled cards, by ID.
+    pub fn diff(&self) -> CatalogDiff<'_> {
+        CatalogDiff {
+            missing: self
+                .items
+                .iter()
+                .filter(|card| Card::from_id(card.id).is_none())
+                .collect(),
+            removed: Card::ALL
+                .into_iter()
+                .filter(|card| self.get(card.id).is_none())
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// A player's upcoming chests, from `/players/{tag}/upcomingchests`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingChests {
+    /// The upcoming chests, in order of when they will be received.
+    /// Only the next few chests and the special ones are listed.
+    pub items: Vec<Chest>,
+}
+
+/// A chest in a player's chest cycle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chest {
+    /// How many chests away this one is; the next chest is `0`.
+    pub index: u32,
+
+    /// The chest's name.
+    pub name: String,
+}
+
+impl UpcomingChests {
+    /// Returns the first upcoming chest whose name contains the given string,
+    /// or `None` if there isn't one in the cycle.
+    pub fn find(&self, name: &str) -> Option<&Chest> {
+        self.items.iter().find(|chest| chest.name.contains(name))
+    }
+}
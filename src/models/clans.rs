@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use super::locations::Location;
+
+/// A clan's summary, as listed by `/clans` searches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClanSummary {
+    /// The clan's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The clan's name.
+    pub name: String,
+
+    /// Who can join the clan (`open`, `inviteOnly` or `closed`).
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The clan's score.
+    pub clan_score: u32,
+
+    /// The clan's war trophies.
+    pub clan_war_trophies: u32,
+
+    /// The trophies needed to join the clan.
+    pub required_trophies: u32,
+
+    /// The clan's donations this week.
+    #[serde(default)]
+    pub donations_per_week: u32,
+
+    /// The clan's number of members.
+    pub members: u32,
+
+    /// The clan's location.
+    pub location: Option<Location>,
+}
+
+/// A member of a clan, from `/clans/{tag}/members`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClanMember {
+    /// The member's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The member's username.
+    pub name: String,
+
+    /// The member's role (`member`, `elder`, `coLeader` or `leader`).
+    pub role: String,
+
+    /// When the member was last online, in the API's time format
+    /// (`20250101T000000.000Z`).
+    pub last_seen: String,
+
+    /// The member's king level.
+    pub exp_level: u32,
+
+    /// The member's trophies.
+    pub trophies: u32,
+
+    /// The member's rank in the clan, by trophies.
+    pub clan_rank: u32,
+
+    /// The cards the member donated this week.
+    pub donations: u32,
+
+    /// The cards the member received this week.
+    pub donations_received: u32,
+}
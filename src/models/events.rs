@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use super::tournaments::GameMode;
+
+/// A global tournament, from `/globaltournaments`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalTournament {
+    /// The tournament's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The tournament's title.
+    pub title: String,
+
+    /// When the tournament starts, in the API's time format.
+    pub start_time: String,
+
+    /// When the tournament ends, in the API's time format.
+    pub end_time: String,
+
+    /// The level cards are set to in the tournament.
+    pub tournament_level: Option<u32>,
+
+    /// The fewest king levels a player needs to join.
+    pub min_exp_level: Option<u32>,
+
+    /// The losses that end a player's run.
+    pub max_losses: Option<u32>,
+
+    /// The tournament's game mode.
+    pub game_mode: Option<GameMode>,
+
+    /// The rewards for reaching win milestones.
+    #[serde(default)]
+    pub milestone_rewards: Vec<Reward>,
+}
+
+/// A group of challenges, from `/challenges`.
+/// Chain challenges unlock one after another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChallengeGroup {
+    /// The kind of group (`singleChallenge` or `chainChallenge`).
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// When the challenges start, in the API's time format.
+    pub start_time: String,
+
+    /// When the challenges end, in the API's time format.
+    pub end_time: String,
+
+    /// The challenges in the group.
+    #[serde(default)]
+    pub challenges: Vec<Challenge>,
+}
+
+/// A challenge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Challenge {
+    /// The challenge's ID.
+    pub id: i64,
+
+    /// The challenge's name.
+    pub name: String,
+
+    /// The challenge's description.
+    #[serde(default)]
+    pub description: String,
+
+    /// The losses that end a player's run.
+    pub max_losses: Option<u32>,
+
+    /// The most wins a run can get.
+    pub max_wins: Option<u32>,
+
+    /// Whether the challenge is casual (losses don't end the run).
+    #[serde(default)]
+    pub casual: bool,
+
+    /// The challenge's game mode.
+    pub game_mode: Option<GameMode>,
+
+    /// The rewards for reaching win milestones.
+    #[serde(default)]
+    pub prizes: Vec<Reward>,
+}
+
+/// A reward of an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reward {
+    /// The kind of reward (e.g. `resource`, `chest`, `card`, `consumable`).
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The amount given.
+    #[serde(default)]
+    pub amount: u32,
+
+    /// The wins needed to get the reward, for milestone rewards.
+    pub wins: Option<u32>,
+
+    /// The resource given (e.g. `gold`), for resource rewards.
+    pub resource: Option<String>,
+
+    /// The chest given, for chest rewards.
+    pub chest: Option<String>,
+
+    /// The item given, for consumable rewards.
+    pub consumable_name: Option<String>,
+
+    /// The card given, for card rewards.
+    pub card: Option<RewardCard>,
+}
+
+/// The card of a card reward.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewardCard {
+    /// The card's name.
+    pub name: String,
+}
+
+impl Reward {
+    /// Returns a short description of the reward (e.g. `100 gold`).
+    pub fn describe(&self) -> String {
+        let item = self
+            .card
+            .as_ref()
+            .map(|card| card.name.as_str())
+            .or(self.consumable_name.as_deref())
+            .or(self.chest.as_deref())
+            .or(self.resource.as_deref())
+            .unwrap_or(self.kind.as_str());
+
+        if self.amount > 1 {
+            format!("{} {item}", self.amount)
+        } else {
+            item.to_string()
+        }
+    }
+}
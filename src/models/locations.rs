@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+/// A location from `/locations`; either a country or a region.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    /// The location's ID.
+    pub id: i64,
+
+    /// The location's name.
+    pub name: String,
+
+    /// Whether the location is a country or a region.
+    pub is_country: bool,
+
+    /// The location's country code, if it's a country.
+    pub country_code: Option<String>,
+}
+
+impl Location {
+    /// Returns `true` if the given query is the location's ID, country code or
+    /// name. Country codes and names are matched case-insensitively.
+    pub fn matches(&self, query: &str) -> bool {
+        self.id.to_string() == query
+            || self.name.eq_ignore_ascii_case(query)
+            || self
+                .country_code
+                .as_deref()
+                .is_some_and(|code| code.eq_ignore_ascii_case(query))
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// A page of a list endpoint's items.
+/// All of the Clash Royale API's list endpoints respond with this.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The items on this page.
+    pub items: Vec<T>,
+
+    /// The cursors to the neighbouring pages.
+    #[serde(default)]
+    pub paging: Paging,
+}
+
+/// The paging info of a `Page`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Paging {
+    /// The cursors to the neighbouring pages.
+    #[serde(default)]
+    pub cursors: Cursors,
+}
+
+/// Cursors to the pages before and after a `Page`.
+/// A cursor is `None` if there is no page in that direction.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cursors {
+    /// The cursor to the next page.
+    pub after: Option<String>,
+
+    /// The cursor to the previous page.
+    pub before: Option<String>,
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{cards::normalize_level, upgrades::Rarity};
+
+use super::cards::IconUrls;
+
+/// A player's profile, from `/players/{tag}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerProfile {
+    /// The player's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The player's username.
+    pub name: String,
+
+    /// The player's king level.
+    pub exp_level: u32,
+
+    /// The player's current trophies.
+    pub trophies: u32,
+
+    /// The cards the player has donated this week.
+    #[serde(default)]
+    pub donations: u32,
+
+    /// The player's unlocked cards.
+    #[serde(default)]
+    pub cards: Vec<PlayerCard>,
+}
+
+/// A card in a player's collection or deck, from `/players/{tag}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerCard {
+    /// The card's name.
+    pub name: String,
+
+    /// The card's in-game ID.
+    pub id: i64,
+
+    /// The card's rarity (`common`, `rare`, `epic`, `legendary` or
+    /// `champion`).
+    #[serde(default)]
+    pub rarity: String,
+
+    /// The card's level, relative to its rarity's starting level.
+    pub level: i64,
+
+    /// The card's max level, relative to its rarity's starting level.
+    pub max_level: i64,
+
+    /// The card's evolution level, or `None` if the player hasn't unlocked it.
+    pub evolution_level: Option<u32>,
+
+    /// The card's max evolution level, or `None` if it has no evolution.
+    pub max_evolution_level: Option<u32>,
+
+    /// The card's star level, or `None` if it has none.
+    pub star_level: Option<u32>,
+
+    /// The card's elixir cost, or `None` if it has none (e.g. Mirror).
+    pub elixir_cost: Option<u32>,
+
+    /// The number of copies of the card the player has towards the next level.
+    #[serde(default)]
+    pub count: u32,
+
+    /// The card's icon URLs.
+    #[serde(default)]
+    pub icon_urls: IconUrls,
+}
+
+impl PlayerCard {
+    /// Returns the card's level as shown in-game.
+    pub fn normalized_level(&self) -> i64 {
+        normalize_level(self.level, self.max_level)
+    }
+
+    /// Returns the card's rarity, or an error if it's unknown.
+    /// If the API left it out, it's found from the max level, which is set by
+    /// the rarity.
+    pub fn rarity(&self) -> Result<Rarity, String> {
+        if !self.rarity.is_empty() {
+            return Rarity::from_name(&self.rarity);
+        }
+
+        Rarity::from_starting_level(normalize_level(1, self.max_level))
+            .ok_or_else(|| format!("Unknown rarity of card {}", self.id))
+    }
+
+    /// Returns `true` if the player has unlocked the card's evolution.
+    pub fn has_evolution(&self) -> bool {
+        self.evolution_level.is_some()
+    }
+}
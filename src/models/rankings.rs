@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// A player's entry in a location's trophy rankings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerRanking {
+    /// The player's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The player's username.
+    pub name: String,
+
+    /// The player's rank.
+    pub rank: u32,
+
+    /// The player's rank in the previous season, or `0` if unranked.
+    #[serde(default)]
+    pub previous_rank: u32,
+
+    /// The player's trophies.
+    pub trophies: u32,
+
+    /// The player's king level.
+    pub exp_level: u32,
+
+    /// The player's clan, if they're in one.
+    pub clan: Option<RankedClan>,
+}
+
+/// A player's entry in the Path of Legends rankings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathOfLegendRanking {
+    /// The player's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The player's username.
+    pub name: String,
+
+    /// The player's rank.
+    pub rank: u32,
+
+    /// The player's rating.
+    pub elo_rating: u32,
+
+    /// The player's king level.
+    pub exp_level: u32,
+
+    /// The player's clan, if they're in one.
+    pub clan: Option<RankedClan>,
+}
+
+/// A clan's entry in a location's clan rankings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClanRanking {
+    /// The clan's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The clan's name.
+    pub name: String,
+
+    /// The clan's rank.
+    pub rank: u32,
+
+    /// The clan's rank in the previous season, or `0` if unranked.
+    #[serde(default)]
+    pub previous_rank: u32,
+
+    /// The clan's score.
+    pub clan_score: u32,
+
+    /// The clan's number of members.
+    pub members: u32,
+}
+
+/// The clan of a ranked player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedClan {
+    /// The clan's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The clan's name.
+    pub name: String,
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+
+/// A clan's current river race, from `/clans/{tag}/currentriverrace`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrentRiverRace {
+    /// The race's state (e.g. `full`, `matchmaking`).
+    pub state: String,
+
+    /// The clan's standing in the race.
+    pub clan: RiverRaceClan,
+
+    /// Every clan in the race, including this one.
+    #[serde(default)]
+    pub clans: Vec<RiverRaceClan>,
+
+    /// The week of the season the race is in, starting at `0`.
+    pub section_index: u32,
+
+    /// The day of the race, starting at `0`.
+    pub period_index: u32,
+
+    /// The kind of day (`training`, `warDay` or `colosseum`).
+    pub period_type: String,
+}
+
+impl CurrentRiverRace {
+    /// Returns whether war decks can be used today, i.e. it isn't a training
+    /// day.
+    pub fn is_war_day(&self) -> bool {
+        matches!(self.period_type.as_str(), "warDay" | "colosseum")
+    }
+}
+
+/// A clan's standing in a river race.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiverRaceClan {
+    /// The clan's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The clan's name.
+    pub name: String,
+
+    /// The clan's fame in the race.
+    #[serde(default)]
+    pub fame: u32,
+
+    /// The clan's repair points in the race.
+    #[serde(default)]
+    pub repair_points: u32,
+
+    /// Everyone who has taken part in the race for this clan, including
+    /// players who have since left it.
+    #[serde(default)]
+    pub participants: Vec<RiverRaceParticipant>,
+}
+
+/// A player's participation in a river race.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiverRaceParticipant {
+    /// The player's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The player's username.
+    pub name: String,
+
+    /// The fame the player earned.
+    pub fame: u32,
+
+    /// The repair points the player earned.
+    pub repair_points: u32,
+
+    /// The player's boat attacks.
+    pub boat_attacks: u32,
+
+    /// The war decks the player used over the whole race.
+    pub decks_used: u32,
+
+    /// The war decks the player used today.
+    pub decks_used_today: u32,
+}
+
+/// A finished river race, from `/clans/{tag}/riverracelog`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiverRaceLogEntry {
+    /// The season the race was in.
+    pub season_id: u32,
+
+    /// The week of the season the race was in, starting at `0`.
+    pub section_index: u32,
+
+    /// When the race ended, in the API's time format (`20250101T000000.000Z`).
+    pub created_date: String,
+
+    /// The final standings of every clan in the race.
+    pub standings: Vec<RiverRaceStanding>,
+}
+
+/// A clan's final standing in a river race.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiverRaceStanding {
+    /// The clan's final rank.
+    pub rank: u32,
+
+    /// The clan war trophies the clan gained or lost.
+    pub trophy_change: i32,
+
+    /// The clan's standing in the race.
+    pub clan: RiverRaceClan,
+}
+
+impl RiverRaceLogEntry {
+    /// Returns the standing of the clan with the given tag, or `None` if it
+    /// wasn't in the race.
+    pub fn standing(&self, clan_tag: &str) -> Option<&RiverRaceStanding> {
+        self.standings
+            .iter()
+            .find(|standing| standing.clan.tag == clan_tag)
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use super::rankings::RankedClan;
+
+/// A tournament, from `/tournaments/{tag}` or `/tournaments` searches.
+/// Searches don't list the tournament's members.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tournament {
+    /// The tournament's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The tournament's name.
+    pub name: String,
+
+    /// The tournament's description.
+    #[serde(default)]
+    pub description: String,
+
+    /// Who can join the tournament (`open` or `passwordProtected`).
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The tournament's status (`inPreparation`, `inProgress` or `ended`).
+    pub status: String,
+
+    /// The tag of the player who created the tournament.
+    pub creator_tag: String,
+
+    /// The highest card level in the tournament.
+    pub level_cap: u32,
+
+    /// The number of players in the tournament.
+    pub capacity: u32,
+
+    /// The most players the tournament can have.
+    pub max_capacity: u32,
+
+    /// The tournament's game mode.
+    pub game_mode: Option<GameMode>,
+
+    /// How long the tournament lasts, in seconds.
+    pub duration: u32,
+
+    /// When the tournament was created, in the API's time format.
+    pub created_time: Option<String>,
+
+    /// When the tournament started, if it has.
+    pub started_time: Option<String>,
+
+    /// When the tournament ended, if it has.
+    pub ended_time: Option<String>,
+
+    /// The tournament's members.
+    #[serde(default)]
+    pub members_list: Vec<TournamentMember>,
+}
+
+/// A game mode, as referenced by tournaments and events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameMode {
+    /// The game mode's ID.
+    pub id: i64,
+
+    /// The game mode's name, which the API doesn't always give.
+    pub name: Option<String>,
+}
+
+/// A member of a tournament.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentMember {
+    /// The player's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The player's username.
+    pub name: String,
+
+    /// The player's score.
+    pub score: u32,
+
+    /// The player's rank.
+    pub rank: u32,
+
+    /// The player's clan, if they're in one.
+    pub clan: Option<RankedClan>,
+}
+
+impl GameMode {
+    /// Returns the game mode's name, or its ID if it doesn't have one.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("Mode {}", self.id))
+    }
+}
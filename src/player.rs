@@ -1,19 +1,35 @@
 mod card;
+mod chests;
+mod collection;
+mod deck;
 mod info;
 
+use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value;
 use std::env::Args;
 
 use crate::{
-    api::{api_call, API_PLAYER_URL},
+    api::{api_call, endpoints},
+    cards::Card,
+    config::{self, OutputFormat},
+    models::players::{PlayerCard, PlayerProfile},
     util::BAD_JSON_ERR_MSG,
 };
 
-use card::get_card_info;
-use info::get_player_info;
+pub use card::{get_card_info, CardInfo};
+pub use chests::{get_chests_info, ChestsInfo};
+pub use collection::{get_collection_info, Collection};
+pub use deck::{get_deck_info, DeckInfo};
+pub use info::{get_player_info, PlayerInfo};
+
+/// The reports that can be asked for about a player, e.g. `--info`.
+pub const REPORT_FLAGS: [&str; 5] = ["--info", "--card", "--collection", "--chests", "--deck"];
 
 /// A player's basic info.
 pub struct Player {
+    /// Player tag, without the leading `#`.
+    tag: String,
+
     /// Clash Royale username.
     username: String,
 
@@ -24,40 +40,131 @@ pub struct Player {
 impl Player {
     /// Player data from the Clash Royale API from the given account ID.
     pub fn new(id: &str) -> Result<Self, String> {
-        // Get player data.
-        let url = format!("{API_PLAYER_URL}{}", id.trim_start_matches('#'));
-        let json = api_call(&url)?;
+        Self::from_json(id, api_call(&endpoints::player(id))?)
+    }
 
+    /// Player data from an already fetched response for the given account ID
+    /// (e.g. a cached one).
+    pub fn from_json(id: &str, json: Value) -> Result<Self, String> {
         // Get the player's username since it's displayed with all data output.
         let username = json
             .get("name")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| BAD_JSON_ERR_MSG)?
+            .ok_or(BAD_JSON_ERR_MSG)?
             .to_string();
 
-        Ok(Self { username, json })
+        let tag = id.trim_start_matches('#').to_string();
+
+        Ok(Self {
+            tag,
+            username,
+            json,
+        })
+    }
+
+    /// Returns the player's tag, without the leading `#`.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns the player's username.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns the player's unlocked cards, or an error if the data is invalid.
+    pub fn cards(&self) -> Result<Vec<PlayerCard>, String> {
+        self.parse_field("cards")
+    }
+
+    /// Returns the cards in the player's current deck, or an error if the data
+    /// is invalid.
+    pub fn current_deck(&self) -> Result<Vec<PlayerCard>, String> {
+        self.parse_field("currentDeck")
+    }
+
+    /// Returns the player's profile, or an error if the data is invalid.
+    pub fn profile(&self) -> Result<PlayerProfile, String> {
+        PlayerProfile::deserialize(&self.json).map_err(|_| BAD_JSON_ERR_MSG.to_string())
+    }
+
+    /// Deserializes the given field of the player's data.
+    fn parse_field<T: DeserializeOwned>(&self, name: &str) -> Result<T, String> {
+        let json = self.json.get(name).ok_or(BAD_JSON_ERR_MSG)?;
+        T::deserialize(json).map_err(|_| BAD_JSON_ERR_MSG.to_string())
     }
 }
 
 /// Parses the input arguments after `-p` and returns the output, or an
 /// error if any are encountered.
+/// The player ID can be left out to use the config profile's player.
 pub fn process_player_commands(mut args: Args) -> Result<(), String> {
-    // Get the player's ID.
-    let id = args.next().ok_or_else(|| "Expected player ID")?;
-    let player = Player::new(&id)?;
+    match args.next() {
+        Some(first) if first.starts_with("--") => process_default_player_commands(first, args),
+        Some(id) => run_player_commands(&id, None, args),
+        None => Err("Expected player ID".to_string()),
+    }
+}
+
+/// Processes the input arguments for the config profile's player, starting
+/// with `first`, e.g. when `-p` is left out.
+/// Returns an error if the profile has no player or any are encountered.
+pub fn process_default_player_commands(first: String, args: Args) -> Result<(), String> {
+    let id = config::profile()
+        .player
+        .clone()
+        .ok_or("Expected player ID (or a `player` in the config)")?;
+
+    run_player_commands(&id, Some(first), args)
+}
+
+/// Processes the input arguments for the player, starting with `first` if
+/// given.
+fn run_player_commands(id: &str, first: Option<String>, args: Args) -> Result<(), String> {
+    let inputs: Vec<_> = first.into_iter().chain(args).collect();
 
-    println!(
-        "- Got player data from ID #{} (username \"{}\")",
-        id, player.username
-    );
+    // Keep JSON output to a single document.
+    let json = config::profile().format == Some(OutputFormat::Json);
+    if json
+        && inputs
+            .iter()
+            .filter(|arg| REPORT_FLAGS.contains(&arg.as_str()))
+            .count()
+            > 1
+    {
+        return Err("Only one report can be printed as JSON at a time".to_string());
+    }
+
+    let player = Player::new(id)?;
+
+    // Keep JSON output parseable.
+    if !json {
+        println!(
+            "- Got player data from ID #{} (username \"{}\")",
+            id, player.username
+        );
+    }
 
+    let mut args = inputs.into_iter();
     while let Some(arg) = args.next() {
         let output = match arg.as_str() {
             // Display general info about the player.
-            "--info" => get_player_info(&mut args, &player)?,
+            "--info" => get_player_info(&player)?,
 
             // Display info about the player's card.
-            "--card" => get_card_info(&mut args, &player)?,
+            "--card" => {
+                let name = args.next().ok_or("Expected card name")?;
+                get_card_info(&player, Card::from_name(&name)?)?
+            }
+
+            // Display the player's unlocked cards and their levels.
+            "--collection" => get_collection_info(&player)?,
+
+            // Display the player's upcoming chests.
+            "--chests" => get_chests_info(&player)?,
+
+            // Display the player's current deck and its copy link.
+            "--deck" => get_deck_info(&player)?,
 
             // Errors:
             _ => return Err(format!("Unexpected input: `{arg}`")),
@@ -1,106 +1,172 @@
+use serde::Serialize;
 use serde_json::Value;
-use std::env::Args;
 
 use crate::{
-    cards::Card,
+    cards::{normalize_level, Card},
+    config::render,
     util::{find_in_json_array, BAD_JSON_ERR_MSG},
 };
 
 use super::Player;
 
-/// Gathers and returns info about the player's card, or returns an error if
-/// any are encountered.
-pub fn get_card_info(args: &mut Args, player: &Player) -> Result<String, String> {
-    let card = Card::from_name(args.next().ok_or_else(|| "Expected card name")?.as_str())?;
-    let mut output = format!("- \"{}\" {}:", player.username, card.name);
+/// A player's progress on a card.
+#[derive(Serialize)]
+pub struct CardInfo {
+    /// The player's username.
+    pub player: String,
 
-    // Find the card's info.
-    let predicate = |v: &Value| {
-        v.get("id")
-            .is_some_and(|v| v.as_i64().is_some_and(|id| id == card.id))
-    };
-    let Some(card_info) = find_in_json_array(&player.json, "cards", predicate) else {
-        // The card is not in the array if it isn't unlocked.
-        // Assume this is the case.
-        output.push_str("\n\tNot unlocked");
-        return Ok(output);
-    };
+    /// The card's name.
+    pub card: &'static str,
+
+    /// Whether the player has unlocked the card.
+    /// The other fields are `None` if not.
+    pub unlocked: bool,
+
+    /// The card's level, relative to level 15.
+    pub level: Option<i64>,
 
-    output.push_str(get_card_level(&card_info)?.as_str());
-    output.push_str(get_card_evo_level(&card_info)?.as_str());
-    output.push_str(get_card_star_level(&card_info)?.as_str());
-    output.push_str(get_card_mastery_level(&player.json, &card)?.as_str());
+    /// Whether the player has unlocked the card's evolution, or `None` if it
+    /// has none.
+    pub evolution_unlocked: Option<bool>,
 
-    Ok(output)
+    /// The card's star level.
+    pub star_level: Option<i64>,
+
+    /// The card's mastery level, or `None` if the player hasn't started it.
+    pub mastery_level: Option<i64>,
+
+    /// The card's max mastery level, or `None` if the player hasn't started
+    /// its mastery.
+    pub max_mastery_level: Option<i64>,
 }
 
-/// Parses the card's level info and returns it in a string.
-/// The string is formatted to be pushed onto `.get_card_info`'s `output`.
+impl CardInfo {
+    /// Gathers the player's progress on the card, or returns an error if the
+    /// data is invalid.
+    pub fn new(player: &Player, card: &'static Card) -> Result<Self, String> {
+        let mut info = Self {
+            player: player.username.clone(),
+            card: card.name,
+            unlocked: false,
+            level: None,
+            evolution_unlocked: None,
+            star_level: None,
+            mastery_level: None,
+            max_mastery_level: None,
+        };
+
+        // Find the card's info.
+        let predicate = |v: &Value| {
+            v.get("id")
+                .is_some_and(|v| v.as_i64().is_some_and(|id| id == card.id))
+        };
+        let Some(card_info) = find_in_json_array(&player.json, "cards", predicate) else {
+            // The card is not in the array if it isn't unlocked.
+            // Assume this is the case.
+            return Ok(info);
+        };
+
+        info.unlocked = true;
+        info.level = Some(get_card_level(card_info)?);
+        info.evolution_unlocked = get_card_evo_unlocked(card_info);
+        info.star_level = Some(get_card_star_level(card_info));
+        if let Some((level, max_level)) = get_card_mastery_level(&player.json, card)? {
+            info.mastery_level = Some(level);
+            info.max_mastery_level = Some(max_level);
+        }
+
+        Ok(info)
+    }
+
+    /// Returns the info as the CLI prints it.
+    pub fn describe(&self) -> String {
+        let mut output = format!("- \"{}\" {}:", self.player, self.card);
+        let Some(level) = self.level else {
+            output.push_str("\n\tNot unlocked");
+            return output;
+        };
+
+        output.push_str(&format!("\n\tLevel: {level}"));
+        if let Some(has_evolution) = self.evolution_unlocked {
+            output.push_str(&format!("\n\tEvo unlocked: {has_evolution}"));
+        }
+        output.push_str(&format!("\n\tStar Level: {}", self.star_level.unwrap_or(0)));
+        match self.mastery_level.zip(self.max_mastery_level) {
+            Some((level, max_level)) => {
+                output.push_str(&format!("\n\tMastery Level: {level}/{max_level}"))
+            }
+            None => output.push_str("\n\tMastery Level 0"),
+        }
+
+        output
+    }
+}
+
+/// Gathers and returns info about the player's card, or returns an error if
+/// any are encountered.
+pub fn get_card_info(player: &Player, card: &'static Card) -> Result<String, String> {
+    render(&CardInfo::new(player, card)?, CardInfo::describe)
+}
+
+/// Parses the card's level relative to level 15.
 ///
 /// The given JSON root must be the card's info.
-fn get_card_level(json: &Value) -> Result<String, String> {
+fn get_card_level(json: &Value) -> Result<i64, String> {
     // We need the level and max level to get it relative to level 15.
     // Level just return the number of times the card has been leveled up (minus one).
     let level = json
         .get("level")
         .and_then(|v| v.as_i64())
-        .ok_or_else(|| BAD_JSON_ERR_MSG)?;
+        .ok_or(BAD_JSON_ERR_MSG)?;
     let max_level = json
         .get("maxLevel")
         .and_then(|v| v.as_i64())
-        .ok_or_else(|| BAD_JSON_ERR_MSG)?;
-    let relative_level = (level - max_level) + 14;
+        .ok_or(BAD_JSON_ERR_MSG)?;
 
-    Ok(format!("\n\tLevel: {relative_level}"))
+    Ok(normalize_level(level, max_level))
 }
 
-/// Parses the card's evolution level and returns its info in a string.
-/// If the card does not have an evolution, returns an empty string.
+/// Parses whether the card's evolution is unlocked.
+/// If the card does not have an evolution, returns `None`.
 ///
 /// The given JSON root must be the card's info.
-fn get_card_evo_level(json: &Value) -> Result<String, String> {
+fn get_card_evo_unlocked(json: &Value) -> Option<bool> {
     // If this field doesn't exist the card doesn't have an evolution.
-    if json.get("maxEvolutionLevel").is_none() {
-        return Ok(String::new());
-    }
+    json.get("maxEvolutionLevel")?;
 
     // If this field exists the player has the evolution; it will only ever be 1.
-    let has_evolution = json.get("evolutionLevel").is_some();
-    Ok(format!("\n\tEvo unlocked: {has_evolution}"))
+    Some(json.get("evolutionLevel").is_some())
 }
 
-/// Parses the card's star level info and returns it in a string.
-/// The string is formatted to be pushed onto `.get_card_info`'s `output`.
+/// Parses the card's star level, which is 0 if it has none.
 ///
 /// The given JSON root must be the card's info.
-fn get_card_star_level(json: &Value) -> Result<String, String> {
-    let star_level = json.get("starLevel").and_then(|v| v.as_i64()).unwrap_or(0);
-
-    Ok(format!("\n\tStar Level: {star_level}"))
+fn get_card_star_level(json: &Value) -> i64 {
+    json.get("starLevel").and_then(|v| v.as_i64()).unwrap_or(0)
 }
 
-/// Parses the card's mastery level info and returns it in a string.
-/// The string is formatted to be pushed onto `.get_card_info`'s `output`.
+/// Parses the card's mastery level and max level, or `None` if the player
+/// hasn't started its mastery.
 ///
 /// The given JSON root must be the player's info.
-fn get_card_mastery_level(json: &Value, card: &Card) -> Result<String, String> {
+fn get_card_mastery_level(json: &Value, card: &Card) -> Result<Option<(i64, i64)>, String> {
     let predicate = |v: &Value| {
         v.get("name")
             .is_some_and(|v| v.as_str().is_some_and(|s| s == card.badge_name))
     };
     let Some(mastery_info) = find_in_json_array(json, "badges", predicate) else {
-        return Ok("\n\tMastery Level 0".to_string());
+        return Ok(None);
     };
 
     // Get level and max level.
     let level = mastery_info
         .get("level")
         .and_then(|v| v.as_i64())
-        .ok_or_else(|| BAD_JSON_ERR_MSG)?;
+        .ok_or(BAD_JSON_ERR_MSG)?;
     let max_level = mastery_info
         .get("maxLevel")
         .and_then(|v| v.as_i64())
-        .ok_or_else(|| BAD_JSON_ERR_MSG)?;
+        .ok_or(BAD_JSON_ERR_MSG)?;
 
-    Ok(format!("\n\tMastery Level: {level}/{max_level}"))
+    Ok(Some((level, max_level)))
 }
@@ -0,0 +1,82 @@
+use serde::Serialize;
+
+use crate::{
+    api::{api_get, endpoints},
+    config::render,
+    models::chests::{Chest, UpcomingChests},
+};
+
+use super::Player;
+
+/// The special chests whose distance in the cycle is always shown.
+const SPECIAL_CHESTS: [&str; 3] = ["Legendary", "Epic", "Magical"];
+
+/// A player's upcoming chests.
+#[derive(Serialize)]
+pub struct ChestsInfo {
+    /// The player's username.
+    pub player: String,
+
+    /// The upcoming chests, in order of when they will be received.
+    pub upcoming: Vec<Chest>,
+
+    /// How far away each special chest is.
+    pub special: Vec<SpecialChest>,
+}
+
+/// How far away a special chest is.
+#[derive(Serialize)]
+pub struct SpecialChest {
+    /// The kind of chest (e.g. `Legendary`).
+    pub name: &'static str,
+
+    /// How many chests away it is, or `None` if it isn't in the upcoming
+    /// chests.
+    pub index: Option<u32>,
+}
+
+impl ChestsInfo {
+    /// Returns the player's upcoming chests, with how far away each special
+    /// chest is.
+    pub fn new(player: &Player, chests: UpcomingChests) -> Self {
+        let special = SPECIAL_CHESTS
+            .iter()
+            .map(|&name| SpecialChest {
+                name,
+                index: chests.find(name).map(|chest| chest.index),
+            })
+            .collect();
+
+        Self {
+            player: player.username.clone(),
+            upcoming: chests.items,
+            special,
+        }
+    }
+
+    /// Returns the chests as the CLI prints them.
+    pub fn describe(&self) -> String {
+        let mut output = format!("- \"{}\" Upcoming Chests:", self.player);
+
+        for chest in &self.upcoming {
+            output.push_str(&format!("\n\t+{}: {}", chest.index, chest.name));
+        }
+
+        for chest in &self.special {
+            output.push_str(&match chest.index {
+                Some(index) => format!("\n\t{} chest in {index} chests", chest.name),
+                None => format!("\n\t{} chest not in upcoming chests", chest.name),
+            });
+        }
+
+        output
+    }
+}
+
+/// Gathers and returns info about the player's upcoming chests, or returns an
+/// error if any are encountered.
+pub fn get_chests_info(player: &Player) -> Result<String, String> {
+    let chests: UpcomingChests = api_get(&endpoints::upcoming_chests(&player.tag))?;
+
+    render(&ChestsInfo::new(player, chests), ChestsInfo::describe)
+}
@@ -0,0 +1,141 @@
+use serde::Serialize;
+
+use crate::{cards::NUM_CARDS, config::render, models::players::PlayerCard};
+
+use super::Player;
+
+/// A player's unlocked cards.
+#[derive(Serialize)]
+pub struct Collection {
+    /// The player's username.
+    pub player: String,
+
+    /// The player's cards, from the highest level to the lowest unless sorted
+    /// otherwise.
+    pub cards: Vec<CollectionCard>,
+
+    /// The number of cards in the game.
+    pub total_cards: usize,
+}
+
+/// A card in a player's collection.
+#[derive(Serialize)]
+pub struct CollectionCard {
+    /// The card's name.
+    pub name: String,
+
+    /// The card's in-game ID.
+    pub id: i64,
+
+    /// The card's rarity.
+    pub rarity: String,
+
+    /// The card's level, relative to level 15.
+    pub level: i64,
+
+    /// Whether the player has unlocked the card's evolution, or `None` if it
+    /// has none.
+    pub evolution_unlocked: Option<bool>,
+
+    /// The card's star level.
+    pub star_level: u32,
+
+    /// The copies of the card the player has towards the next level.
+    pub count: u32,
+}
+
+impl Collection {
+    /// Gathers the player's cards, or returns an error if the data is invalid.
+    pub fn new(player: &Player) -> Result<Self, String> {
+        let cards = player
+            .cards()?
+            .iter()
+            .map(|card: &PlayerCard| CollectionCard {
+                name: card.name.clone(),
+                id: card.id,
+                rarity: card.rarity.clone(),
+                level: card.normalized_level(),
+                evolution_unlocked: card
+                    .max_evolution_level
+                    .map(|_| card.evolution_level.is_some()),
+                star_level: card.star_level.unwrap_or(0),
+                count: card.count,
+            })
+            .collect();
+
+        let mut collection = Self {
+            player: player.username.clone(),
+            cards,
+            total_cards: NUM_CARDS,
+        };
+        collection.sort("level")?;
+
+        Ok(collection)
+    }
+
+    /// Sorts the cards by `level`, `name`, `rarity` or `count`, highest first
+    /// for numbers (then by name).
+    /// Returns an error if the key is unknown.
+    pub fn sort(&mut self, key: &str) -> Result<(), String> {
+        match key {
+            "level" => self
+                .cards
+                .sort_by(|a, b| b.level.cmp(&a.level).then_with(|| a.name.cmp(&b.name))),
+            "name" => self.cards.sort_by(|a, b| a.name.cmp(&b.name)),
+            "rarity" => self.cards.sort_by(|a, b| {
+                rarity_rank(&b.rarity)
+                    .cmp(&rarity_rank(&a.rarity))
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+            "count" => self
+                .cards
+                .sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name))),
+            _ => {
+                return Err(format!(
+                    "Unknown sort `{key}` (`level`, `name`, `rarity` or `count`)"
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the collection as the CLI prints it.
+    pub fn describe(&self) -> String {
+        let mut output = format!(
+            "- \"{}\" Collection ({}/{} unlocked):",
+            self.player,
+            self.cards.len(),
+            self.total_cards
+        );
+
+        for card in &self.cards {
+            output.push_str(&format!("\n\t{}: Level {}", card.name, card.level));
+            if card.evolution_unlocked == Some(true) {
+                output.push_str(", Evo");
+            }
+            if card.star_level > 0 {
+                output.push_str(&format!(", {} Stars", card.star_level));
+            }
+        }
+
+        output
+    }
+}
+
+/// Gathers and returns the player's unlocked cards and their levels, or
+/// returns an error if any are encountered.
+pub fn get_collection_info(player: &Player) -> Result<String, String> {
+    render(&Collection::new(player)?, Collection::describe)
+}
+
+/// Returns the rarity's rank, from `common` (0) to `champion` (4).
+fn rarity_rank(rarity: &str) -> u8 {
+    match rarity {
+        "rare" => 1,
+        "epic" => 2,
+        "legendary" => 3,
+        "champion" => 4,
+        _ => 0,
+    }
+}
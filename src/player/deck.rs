@@ -0,0 +1,61 @@
+use serde::Serialize;
+
+use crate::{
+    config::render,
+    deck::{
+        archetype::{ArchetypeRules, Classification},
+        Deck,
+    },
+};
+
+use super::Player;
+
+/// A player's current deck.
+#[derive(Serialize)]
+pub struct DeckInfo {
+    /// The player's username.
+    pub player: String,
+
+    /// The names of the deck's cards, in order.
+    pub cards: Vec<&'static str>,
+
+    /// The deck's copy link.
+    pub link: String,
+
+    /// The deck's archetype, win conditions and spells.
+    #[serde(flatten)]
+    pub classification: Classification,
+}
+
+impl DeckInfo {
+    /// Gathers the player's current deck and classifies it, or returns an
+    /// error if the data or archetype rules are invalid.
+    pub fn new(player: &Player) -> Result<Self, String> {
+        let cards = player.current_deck()?;
+        let deck = Deck::from_player_cards(&cards)?;
+
+        Ok(Self {
+            player: player.username.clone(),
+            cards: deck.cards.iter().map(|card| card.name).collect(),
+            link: deck.to_link(),
+            classification: ArchetypeRules::load(None)?.classify_player_cards(&cards)?,
+        })
+    }
+
+    /// Returns the deck as the CLI prints it.
+    pub fn describe(&self) -> String {
+        format!(
+            "- \"{}\" Current Deck:\n\t{}\n\tLink: {}{}",
+            self.player,
+            self.cards.join(", "),
+            self.link,
+            self.classification.describe()
+        )
+    }
+}
+
+/// Gathers and returns the player's current deck, its copy link and its
+/// archetype, or returns an error if any are encountered.
+pub fn get_deck_info(player: &Player) -> Result<String, String> {
+    render(&DeckInfo::new(player)?, DeckInfo::describe)
+}
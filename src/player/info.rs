@@ -1,68 +1,125 @@
-use std::env::Args;
+use serde::Serialize;
 
-use crate::{cards::NUM_CARDS, player::Player, util::BAD_JSON_ERR_MSG};
+use crate::{cards::NUM_CARDS, config::render, player::Player, util::BAD_JSON_ERR_MSG};
 
-pub fn get_player_info(_: &mut Args, player: &Player) -> Result<String, String> {
-    let mut output = format!("- \"{}\" Info:", player.username);
-    output.push_str(get_win_loss_info(player)?.as_str());
-    output.push_str(get_card_collection_info(player)?.as_str());
-    output.push_str(get_clan_info(player)?.as_str());
+/// A player's general info.
+#[derive(Serialize)]
+pub struct PlayerInfo {
+    /// The player's tag, without the leading `#`.
+    pub tag: String,
 
-    Ok(output)
-}
+    /// The player's username.
+    pub name: String,
+
+    /// The player's total wins.
+    pub wins: i64,
+
+    /// The player's total losses.
+    pub losses: i64,
+
+    /// The percentage of the player's games won, or `0` if they haven't
+    /// played any.
+    pub win_rate: f64,
+
+    /// The number of cards the player has unlocked.
+    pub cards_unlocked: usize,
 
-/// Returns a string containing the player's win/loss numbers an ratio.
-fn get_win_loss_info(player: &Player) -> Result<String, String> {
-    let wins = player
-        .json
-        .get("wins")
-        .and_then(|v| v.as_i64())
-        .ok_or_else(|| BAD_JSON_ERR_MSG)?;
-    let losses = player
-        .json
-        .get("losses")
-        .and_then(|v| v.as_i64())
-        .ok_or_else(|| BAD_JSON_ERR_MSG)?;
-    let ratio = 100. * wins as f64 / (wins + losses) as f64;
-
-    Ok(format!(
-        "\n\t{wins} wins, {losses} losses ({ratio:.2}% winrate)"
-    ))
+    /// The number of cards in the game.
+    pub total_cards: usize,
+
+    /// The player's clan, if they're in one.
+    pub clan: Option<PlayerClan>,
 }
 
-/// Returns a string containing the player's card collection info.
-fn get_card_collection_info(player: &Player) -> Result<String, String> {
-    let num_unlocked = player
-        .json
-        .get("cards")
-        .and_then(|v| v.as_array())
-        .map(|arr| arr.len())
-        .ok_or_else(|| BAD_JSON_ERR_MSG)?;
+/// A player's clan and role in it.
+#[derive(Serialize)]
+pub struct PlayerClan {
+    /// The clan's tag, with the leading `#`.
+    pub tag: String,
+
+    /// The clan's name.
+    pub name: String,
 
-    Ok(format!("\n\tCards Unlocked: {num_unlocked}/{NUM_CARDS}"))
+    /// The player's role in the clan.
+    pub role: String,
 }
 
-/// Returns a string containing the player's clan's info.
-fn get_clan_info(player: &Player) -> Result<String, String> {
-    let info = match player.json.get("clan") {
-        Some(clan_info) => {
-            let role = player
+impl PlayerInfo {
+    /// Gathers the player's info, or returns an error if the data is invalid.
+    pub fn new(player: &Player) -> Result<Self, String> {
+        let number = |name| {
+            player
                 .json
-                .get("role")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| BAD_JSON_ERR_MSG)?;
-            let id = clan_info
-                .get("tag")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| BAD_JSON_ERR_MSG)?;
-            let name = clan_info
-                .get("name")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| BAD_JSON_ERR_MSG)?;
-            format!("{role} of {id}; \"{name}\"")
-        }
-        None => "None".to_string(),
+                .get(name)
+                .and_then(|v| v.as_i64())
+                .ok_or(BAD_JSON_ERR_MSG)
+        };
+        let wins = number("wins")?;
+        let losses = number("losses")?;
+        let win_rate = if wins + losses > 0 {
+            100. * wins as f64 / (wins + losses) as f64
+        } else {
+            0.
+        };
+
+        let cards_unlocked = player
+            .json
+            .get("cards")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.len())
+            .ok_or(BAD_JSON_ERR_MSG)?;
+
+        Ok(Self {
+            tag: player.tag.clone(),
+            name: player.username.clone(),
+            wins,
+            losses,
+            win_rate,
+            cards_unlocked,
+            total_cards: NUM_CARDS,
+            clan: get_clan(player)?,
+        })
+    }
+
+    /// Returns the info as the CLI prints it.
+    pub fn describe(&self) -> String {
+        let clan = match &self.clan {
+            Some(clan) => format!("{} of {}; \"{}\"", clan.role, clan.tag, clan.name),
+            None => "None".to_string(),
+        };
+
+        format!(
+            "- \"{}\" Info:\
+            \n\t{} wins, {} losses ({:.2}% winrate)\
+            \n\tCards Unlocked: {}/{}\
+            \n\tClan: {clan}",
+            self.name, self.wins, self.losses, self.win_rate, self.cards_unlocked, self.total_cards
+        )
+    }
+}
+
+/// Gathers and returns the player's general info, or returns an error if any
+/// are encountered.
+pub fn get_player_info(player: &Player) -> Result<String, String> {
+    render(&PlayerInfo::new(player)?, PlayerInfo::describe)
+}
+
+/// Returns the player's clan and role, or `None` if they aren't in one.
+fn get_clan(player: &Player) -> Result<Option<PlayerClan>, String> {
+    let Some(clan_info) = player.json.get("clan") else {
+        return Ok(None);
+    };
+
+    let field = |json: &serde_json::Value, name| {
+        json.get(name)
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or(BAD_JSON_ERR_MSG)
     };
 
-    Ok(format!("\n\tClan: {info}"))
+    Ok(Some(PlayerClan {
+        tag: field(clan_info, "tag")?,
+        name: field(clan_info, "name")?,
+        role: field(&player.json, "role")?,
+    }))
 }
@@ -0,0 +1,225 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::env::Args;
+
+use crate::{
+    api::{
+        api_get,
+        endpoints::{self, with_query, PageQuery},
+        paging::paginate,
+    },
+    config::render,
+    models::{
+        locations::Location,
+        paging::{Page, Paging},
+        rankings::{ClanRanking, PathOfLegendRanking, PlayerRanking, RankedClan},
+    },
+    util::next_number,
+};
+
+/// The location ID the API uses for global rankings.
+const GLOBAL_LOCATION_ID: &str = "global";
+
+/// A page of rankings, or all of them.
+#[derive(Serialize)]
+struct RankingsReport<T> {
+    /// What the rankings are (e.g. `Player rankings in Global`).
+    title: String,
+
+    /// The ranked players or clans, from the top.
+    entries: Vec<T>,
+
+    /// The cursors to the neighbouring pages, or `None` if every entry was
+    /// gathered.
+    paging: Option<Paging>,
+}
+
+/// The rankings to display.
+enum Rankings {
+    /// Player trophy rankings.
+    Players,
+
+    /// Clan rankings.
+    Clans,
+
+    /// Path of Legends rankings, of the given season or the current one.
+    PathOfLegend(Option<String>),
+}
+
+/// Parses the input arguments after `rankings` and prints the output, or
+/// returns an error if any are encountered.
+pub fn process_rankings_commands(mut args: Args) -> Result<(), String> {
+    let query = args
+        .next()
+        .ok_or("Expected location name, country code or ID")?;
+    let mut rankings = Rankings::Players;
+    let mut page = PageQuery::default();
+    let mut all = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Which rankings to display.
+            "--players" => rankings = Rankings::Players,
+            "--clans" => rankings = Rankings::Clans,
+            "--pol" => rankings = Rankings::PathOfLegend(None),
+            "--season" => {
+                let season = args.next().ok_or("Expected season ID (e.g. `2025-10`)")?;
+                rankings = Rankings::PathOfLegend(Some(season));
+            }
+
+            // Pagination.
+            "--limit" => page.limit = Some(next_number(&mut args, "page limit")?),
+            "--after" => page.after = Some(args.next().ok_or("Expected cursor")?),
+            "--before" => page.before = Some(args.next().ok_or("Expected cursor")?),
+            "--all" => all = true,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let (location_id, location_name) = find_location(&query)?;
+    let output = match rankings {
+        Rankings::Players => get_rankings(
+            &endpoints::player_rankings(&location_id),
+            &format!("Player rankings in {location_name}"),
+            &page,
+            all,
+            format_player_ranking,
+        )?,
+        Rankings::Clans => get_rankings(
+            &endpoints::clan_rankings(&location_id),
+            &format!("Clan rankings in {location_name}"),
+            &page,
+            all,
+            format_clan_ranking,
+        )?,
+        Rankings::PathOfLegend(None) => get_rankings(
+            &endpoints::path_of_legend_rankings(&location_id),
+            &format!("Path of Legends rankings in {location_name}"),
+            &page,
+            all,
+            format_path_of_legend_ranking,
+        )?,
+        Rankings::PathOfLegend(Some(season)) => get_rankings(
+            &endpoints::path_of_legend_season_rankings(&season),
+            &format!("Global Path of Legends rankings of season {season}"),
+            &page,
+            all,
+            format_path_of_legend_ranking,
+        )?,
+    };
+
+    println!("{output}");
+
+    Ok(())
+}
+
+/// Looks up the location by its name, country code or ID and returns its ID
+/// and name, or an error if there's no such location.
+pub(crate) fn find_location(query: &str) -> Result<(String, String), String> {
+    if query.eq_ignore_ascii_case(GLOBAL_LOCATION_ID) {
+        return Ok((GLOBAL_LOCATION_ID.to_string(), "Global".to_string()));
+    }
+
+    let locations: Page<Location> = api_get(&endpoints::locations())?;
+    locations
+        .items
+        .into_iter()
+        .find(|location| location.matches(query))
+        .map(|location| (location.id.to_string(), location.name))
+        .ok_or_else(|| format!("Unknown location \"{query}\""))
+}
+
+/// Gathers the rankings at the given URL and returns them in the profile's
+/// output format, with each entry formatted by `format_entry` as text.
+///
+/// If `all` is set, every entry after the page query's cursor (up to its limit)
+/// is gathered; otherwise only the one page is.
+fn get_rankings<T: DeserializeOwned + Serialize>(
+    url: &str,
+    title: &str,
+    page: &PageQuery,
+    all: bool,
+    format_entry: fn(&T) -> String,
+) -> Result<String, String> {
+    let report = if all {
+        RankingsReport {
+            title: title.to_string(),
+            entries: paginate(url, page).collect::<Result<_, _>>()?,
+            paging: None,
+        }
+    } else {
+        let rankings: Page<T> = api_get(&with_query(url, &page.params())?)?;
+        RankingsReport {
+            title: title.to_string(),
+            entries: rankings.items,
+            paging: Some(rankings.paging),
+        }
+    };
+
+    render(&report, |report| {
+        let mut output = format!("- {}:", report.title);
+        for entry in &report.entries {
+            output.push_str(&format_entry(entry));
+        }
+        if let Some(paging) = &report.paging {
+            output.push_str(get_paging_info(paging).as_str());
+        }
+
+        output
+    })
+}
+
+/// Returns a string containing a player's trophy ranking.
+fn format_player_ranking(player: &PlayerRanking) -> String {
+    format!(
+        "\n\t#{} \"{}\" ({}): {} trophies, King Level {}{}",
+        player.rank,
+        player.name,
+        player.tag,
+        player.trophies,
+        player.exp_level,
+        get_ranked_clan_info(player.clan.as_ref()),
+    )
+}
+
+/// Returns a string containing a clan's ranking.
+fn format_clan_ranking(clan: &ClanRanking) -> String {
+    format!(
+        "\n\t#{} \"{}\" ({}): {} score, {}/50 members",
+        clan.rank, clan.name, clan.tag, clan.clan_score, clan.members,
+    )
+}
+
+/// Returns a string containing a player's Path of Legends ranking.
+fn format_path_of_legend_ranking(player: &PathOfLegendRanking) -> String {
+    format!(
+        "\n\t#{} \"{}\" ({}): {} rating, King Level {}{}",
+        player.rank,
+        player.name,
+        player.tag,
+        player.elo_rating,
+        player.exp_level,
+        get_ranked_clan_info(player.clan.as_ref()),
+    )
+}
+
+/// Returns a string containing a ranked player's clan, or an empty string if
+/// they aren't in one.
+fn get_ranked_clan_info(clan: Option<&RankedClan>) -> String {
+    clan.map(|clan| format!(", Clan \"{}\"", clan.name))
+        .unwrap_or_default()
+}
+
+/// Returns a string containing the arguments to get the neighbouring pages.
+fn get_paging_info(paging: &Paging) -> String {
+    let mut output = String::new();
+    if let Some(after) = &paging.cursors.after {
+        output.push_str(&format!("\n\tNext page: `--after {after}`"));
+    }
+    if let Some(before) = &paging.cursors.before {
+        output.push_str(&format!("\n\tPrevious page: `--before {before}`"));
+    }
+
+    output
+}
@@ -0,0 +1,225 @@
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Context, Editor, Helper,
+};
+use std::env::Args;
+
+use crate::{
+    api::tokens::redact,
+    cards::Card,
+    config::render,
+    player::{get_card_info, get_chests_info, get_deck_info, get_player_info, Collection, Player},
+};
+
+/// The file the REPL's command history is kept in if no other is given.
+const DEFAULT_HISTORY_PATH: &str = ".parse_royale_history";
+
+/// The REPL's commands, for tab-completion.
+const COMMANDS: [&str; 9] = [
+    "info",
+    "card",
+    "collection",
+    "deck",
+    "chests",
+    "diff",
+    "reload",
+    "help",
+    "quit",
+];
+
+/// The keys `collection` can sort by.
+const SORT_KEYS: [&str; 4] = ["level", "name", "rarity", "count"];
+
+/// The string printed for `help`.
+const HELP_STR: &str = "- List of commands:\
+    \n\t`info`: Get the player's info\
+    \n\t`card NAME`: Get the player's progress on a card, e.g. `card hog-rider`\
+    \n\t`collection [level|name|rarity|count]`: List the player's cards, sorted\
+    \n\t`deck`: Get the player's current deck\
+    \n\t`chests`: Get the player's upcoming chests\
+    \n\t`diff`: Refetch the player and show what changed\
+    \n\t`reload`: Refetch the player\
+    \n\t`quit`: Exit";
+
+/// Completes the REPL's commands, card names and sort keys.
+struct ReplHelper {
+    /// Every card's kebab-case name.
+    card_names: Vec<String>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind(' ').map_or(0, |i| i + 1);
+        let word = &line[start..];
+
+        let candidates: Vec<&str> = match line.split_whitespace().next() {
+            _ if start == 0 => COMMANDS.to_vec(),
+            Some("card") => self.card_names.iter().map(String::as_str).collect(),
+            Some("collection") => SORT_KEYS.to_vec(),
+            _ => Vec::new(),
+        };
+
+        Ok((
+            start,
+            candidates
+                .into_iter()
+                .filter(|candidate| candidate.starts_with(word))
+                .map(str::to_string)
+                .collect(),
+        ))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Parses the input arguments after `repl`, loads the player and runs commands
+/// on them until `quit`, or returns an error if any are encountered.
+pub fn process_repl_commands(mut args: Args) -> Result<(), String> {
+    let id = args.next().ok_or("Expected player ID")?;
+    let mut history_path = DEFAULT_HISTORY_PATH.to_string();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // Where command history is kept.
+            "--history" => history_path = args.next().ok_or("Expected history file")?,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let mut player = Player::new(&id)?;
+    let mut editor: Editor<ReplHelper, DefaultHistory> =
+        Editor::new().map_err(|e| format!("Failed to start the REPL: {e}"))?;
+    editor.set_helper(Some(ReplHelper {
+        card_names: Card::ALL.iter().map(|card| card.kebab_name()).collect(),
+    }));
+    // There's no history the first time.
+    let _ = editor.load_history(&history_path);
+
+    println!(
+        "- Loaded player #{} (username \"{}\"); `help` for commands",
+        player.tag(),
+        player.username()
+    );
+
+    loop {
+        let line = match editor.readline(&format!("{}> ", player.username())) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(format!("Failed to read command: {e}")),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let _ = editor.add_history_entry(line);
+
+        let mut words = line.split_whitespace();
+        let result = match (words.next().unwrap_or_default(), words.next()) {
+            ("quit" | "exit", _) => break,
+            ("help", _) => Ok(HELP_STR.to_string()),
+            ("info", _) => get_player_info(&player),
+            ("card", Some(name)) => {
+                Card::from_name(name).and_then(|card| get_card_info(&player, card))
+            }
+            ("card", None) => Err("Expected card name".to_string()),
+            ("collection", sort) => get_collection_info(&player, sort.unwrap_or("level")),
+            ("deck", _) => get_deck_info(&player),
+            ("chests", _) => get_chests_info(&player),
+            ("diff", _) => Player::new(player.tag()).and_then(|new| {
+                let output = get_diff_info(&player, &new);
+                player = new;
+                output
+            }),
+            ("reload", _) => Player::new(player.tag()).map(|new| {
+                player = new;
+                format!("- Reloaded player #{}", player.tag())
+            }),
+
+            // Errors:
+            (command, _) => Err(format!("Unknown command `{command}`; `help` for commands")),
+        };
+
+        match result {
+            Ok(output) => println!("{output}"),
+            Err(e) => eprintln!("{}", redact(&e)),
+        }
+    }
+
+    editor
+        .save_history(&history_path)
+        .map_err(|_| format!("Failed to write history to `{history_path}`"))
+}
+
+/// Returns the player's cards sorted by the given key as a string.
+fn get_collection_info(player: &Player, sort: &str) -> Result<String, String> {
+    let mut collection = Collection::new(player)?;
+    collection.sort(sort)?;
+
+    render(&collection, Collection::describe)
+}
+
+/// Returns a string containing what changed between the player's old and new
+/// data, or an error if either is invalid.
+fn get_diff_info(old: &Player, new: &Player) -> Result<String, String> {
+    let (old, new) = (old.profile()?, new.profile()?);
+    let mut output = format!("- \"{}\" Changes Since Loaded:", new.name);
+    let mut changes = Vec::new();
+
+    if new.trophies != old.trophies {
+        changes.push(format!(
+            "Trophies: {} -> {} ({:+})",
+            old.trophies,
+            new.trophies,
+            new.trophies as i64 - old.trophies as i64
+        ));
+    }
+    if new.exp_level != old.exp_level {
+        changes.push(format!(
+            "King Level: {} -> {}",
+            old.exp_level, new.exp_level
+        ));
+    }
+
+    for card in &new.cards {
+        let Some(old_card) = old.cards.iter().find(|old_card| old_card.id == card.id) else {
+            changes.push(format!("New Card: {}", card.name));
+            continue;
+        };
+
+        let (old_level, level) = (old_card.normalized_level(), card.normalized_level());
+        if level != old_level {
+            changes.push(format!("{}: Level {old_level} -> {level}", card.name));
+        }
+        if card.evolution_level.is_some() && old_card.evolution_level.is_none() {
+            changes.push(format!("{}: Evo unlocked", card.name));
+        }
+    }
+
+    if changes.is_empty() {
+        output.push_str("\n\tNo changes");
+    }
+    for change in changes {
+        output.push_str(&format!("\n\t{change}"));
+    }
+
+    Ok(output)
+}
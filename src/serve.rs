@@ -0,0 +1,223 @@
+use serde::Serialize;
+use serde_json::{json, to_string, to_value, Value};
+use std::{env::Args, thread, time::Duration};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{info, warn};
+
+use crate::{
+    api::{cache::ResponseCache, endpoints, tokens::redact},
+    cards::Card,
+    clan::{war::get_race_log, AuditReport, Thresholds},
+    config,
+    logging::log_url,
+    models::{battles::Battle, chests::UpcomingChests, clans::ClanMember, paging::Page},
+    player::{CardInfo, Collection, Player, PlayerInfo},
+    util::{next_number, BAD_JSON_ERR_MSG},
+};
+
+/// The address the server listens on if no other is given.
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 8080;
+
+/// The seconds API responses are cached for if no other time is given.
+const DEFAULT_TTL: u64 = 60;
+
+/// The number of requests handled at once if no other is given.
+const DEFAULT_WORKERS: usize = 4;
+
+/// An error response's status code and message.
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl From<String> for HttpError {
+    /// Treats the error as the Clash Royale API's, so not found if it said so
+    /// and a bad gateway otherwise.
+    fn from(message: String) -> Self {
+        let message = redact(&message);
+        let status = if message.contains("\"notFound\"") {
+            404
+        } else {
+            502
+        };
+
+        Self { status, message }
+    }
+}
+
+impl HttpError {
+    /// Returns an error with the given status code and message.
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parses the input arguments after `serve` and runs the REST API server until
+/// stopped, or returns an error if any are encountered.
+pub fn process_serve_commands(mut args: Args) -> Result<(), String> {
+    let mut host = DEFAULT_HOST.to_string();
+    let mut port = DEFAULT_PORT;
+    let mut ttl = config::profile().cache_ttl.unwrap_or(DEFAULT_TTL);
+    let mut workers = DEFAULT_WORKERS;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The address to listen on.
+            "--host" => host = args.next().ok_or("Expected host")?,
+            "--port" => port = next_number(&mut args, "port")?,
+
+            // The seconds to cache API responses for.
+            "--ttl" => ttl = next_number(&mut args, "cache time")?,
+
+            // The number of requests to handle at once.
+            "--workers" => workers = next_number(&mut args, "worker count")?,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    if workers == 0 {
+        return Err("Expected at least 1 worker".to_string());
+    }
+
+    let server = Server::http((host.as_str(), port))
+        .map_err(|e| format!("Failed to listen on {host}:{port}: {e}"))?;
+    let cache = ResponseCache::new(Duration::from_secs(ttl));
+
+    println!("- Serving on http://{host}:{port} with {workers} workers");
+
+    // Each worker takes the next request, so a slow API call doesn't hold up
+    // the others.
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Ok(request) = server.recv() {
+                    handle_request(request, &cache);
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+/// Responds to the request with its route's JSON report, or an error.
+fn handle_request(request: Request, cache: &ResponseCache) {
+    let result = if *request.method() == Method::Get {
+        route(request.url(), cache)
+    } else {
+        Err(HttpError::new(405, "Only GET requests are supported"))
+    };
+    let (status, body) = match result {
+        Ok(json) => (200, json),
+        Err(e) => (e.status, json!({ "error": e.message })),
+    };
+
+    info!(
+        method = %request.method(),
+        path = %log_url(request.url()),
+        status,
+        "Served request"
+    );
+
+    let body = to_string(&body).unwrap_or_else(|_| BAD_JSON_ERR_MSG.to_string());
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(e) = request.respond(response) {
+        warn!(error = %e, "Failed to respond");
+    }
+}
+
+/// Returns the JSON report for the URL's path, or an error if the path is
+/// unknown or the report can't be made.
+fn route(url: &str, cache: &ResponseCache) -> Result<Value, HttpError> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<_> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let tag = |tag: &str| {
+        tag.trim_start_matches("%23")
+            .trim_start_matches('#')
+            .to_string()
+    };
+
+    match segments.as_slice() {
+        // Player reports.
+        ["players", id, "info"] => report(&PlayerInfo::new(&get_player(&tag(id), cache)?)?),
+        ["players", id, "cards", name] => {
+            let card = Card::from_name(name).map_err(|e| HttpError::new(400, e))?;
+            report(&CardInfo::new(&get_player(&tag(id), cache)?, card)?)
+        }
+        ["players", id, "collection"] => report(&Collection::new(&get_player(&tag(id), cache)?)?),
+        ["players", id, "chests"] => {
+            report(&cache.get::<UpcomingChests>(&endpoints::upcoming_chests(&tag(id)))?)
+        }
+        ["players", id, "battles"] => {
+            report(&cache.get::<Vec<Battle>>(&endpoints::battle_log(&tag(id)))?)
+        }
+
+        // Clan reports.
+        ["clans", id, "audit"] => {
+            let thresholds = parse_thresholds(query)?;
+            report(&get_audit(&tag(id), thresholds, cache)?)
+        }
+
+        // Errors:
+        _ => Err(HttpError::new(404, format!("Unknown path `{path}`"))),
+    }
+}
+
+/// Returns the report as JSON.
+fn report<T: Serialize>(report: &T) -> Result<Value, HttpError> {
+    to_value(report).map_err(|_| HttpError::new(500, BAD_JSON_ERR_MSG))
+}
+
+/// Returns the player with the given tag, from the cache if it's there.
+fn get_player(tag: &str, cache: &ResponseCache) -> Result<Player, String> {
+    Player::from_json(tag, cache.call(&endpoints::player(tag))?)
+}
+
+/// Returns the audit of the clan with the given tag, using the cached member
+/// list if it's there.
+/// The river race log is paged through as the CLI's audit does, so it isn't
+/// cached.
+fn get_audit(
+    tag: &str,
+    thresholds: Thresholds,
+    cache: &ResponseCache,
+) -> Result<AuditReport, String> {
+    let members = cache.get::<Page<ClanMember>>(&endpoints::clan_members(tag))?;
+    let log = get_race_log(tag, thresholds.weeks)?;
+
+    AuditReport::new(tag, members.items, &log, thresholds)
+}
+
+/// Parses the audit thresholds from the URL's query (e.g. `weeks=4`), using
+/// the defaults for any left out.
+fn parse_thresholds(query: &str) -> Result<Thresholds, HttpError> {
+    let mut thresholds = Thresholds::default();
+
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let invalid = || HttpError::new(400, format!("Invalid query parameter `{pair}`"));
+
+        match name {
+            "inactive_days" => thresholds.inactive_days = value.parse().map_err(|_| invalid())?,
+            "min_donations" => thresholds.min_donations = value.parse().map_err(|_| invalid())?,
+            "min_war_decks" => thresholds.min_war_decks = value.parse().map_err(|_| invalid())?,
+            "weeks" => {
+                thresholds.weeks = value.parse().map_err(|_| invalid())?;
+                if thresholds.weeks == 0 {
+                    return Err(HttpError::new(400, "The audit needs at least 1 week"));
+                }
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(thresholds)
+}
@@ -0,0 +1,120 @@
+use std::env::Args;
+
+use crate::{
+    api::{
+        api_get,
+        endpoints::{self, PageQuery},
+        paging::paginate,
+    },
+    config::render,
+    models::tournaments::Tournament,
+    util::next_number,
+};
+
+/// The number of tournaments listed by searches if no limit is given.
+const DEFAULT_LIMIT: u32 = 25;
+
+/// Parses the input arguments after `tournament` and prints the output, or
+/// returns an error if any are encountered.
+pub fn process_tournament_commands(mut args: Args) -> Result<(), String> {
+    let output = match args.next().as_deref() {
+        // Search for tournaments by name.
+        Some("search") => {
+            let name = args.next().ok_or("Expected tournament name")?;
+            let mut page = PageQuery {
+                limit: Some(DEFAULT_LIMIT),
+                ..Default::default()
+            };
+
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--limit" => page.limit = Some(next_number(&mut args, "result limit")?),
+
+                    // Errors:
+                    _ => return Err(format!("Unexpected input: `{arg}`")),
+                }
+            }
+
+            let tournaments = paginate(&endpoints::tournament_search(&name)?, &page)
+                .collect::<Result<Vec<Tournament>, _>>()?;
+            render(&tournaments, |tournaments| get_search_results(tournaments))?
+        }
+
+        // Info about a single tournament.
+        Some(tag) => {
+            let tournament: Tournament = api_get(&endpoints::tournament(tag))?;
+            render(&tournament, get_tournament_info)?
+        }
+
+        None => return Err("Expected tournament tag or `search`".to_string()),
+    };
+
+    println!("{output}");
+
+    Ok(())
+}
+
+/// Returns a string listing the tournaments found by a search.
+fn get_search_results(tournaments: &[Tournament]) -> String {
+    let mut output = format!("- Found {} tournaments:", tournaments.len());
+
+    for tournament in tournaments {
+        output.push_str(&format!(
+            "\n\t\"{}\" ({}): {}, {}/{} players, level {} cap",
+            tournament.name,
+            tournament.tag,
+            tournament.status,
+            tournament.capacity,
+            tournament.max_capacity,
+            tournament.level_cap
+        ));
+    }
+
+    output
+}
+
+/// Returns a string containing the tournament's info and standings.
+fn get_tournament_info(tournament: &Tournament) -> String {
+    let mut output = format!("- Tournament \"{}\" ({}):", tournament.name, tournament.tag);
+
+    let game_mode = tournament
+        .game_mode
+        .as_ref()
+        .map_or("Unknown".to_string(), |mode| mode.display_name());
+    let creator = tournament
+        .members_list
+        .iter()
+        .find(|member| member.tag == tournament.creator_tag)
+        .map_or(tournament.creator_tag.clone(), |member| {
+            format!("\"{}\" ({})", member.name, member.tag)
+        });
+
+    output.push_str(&format!("\n\tStatus: {}", tournament.status));
+    output.push_str(&format!(
+        "\n\tCapacity: {}/{}",
+        tournament.capacity, tournament.max_capacity
+    ));
+    output.push_str(&format!("\n\tGame Mode: {game_mode}"));
+    output.push_str(&format!("\n\tLevel Cap: {}", tournament.level_cap));
+    output.push_str(&format!("\n\tCreator: {creator}"));
+    if !tournament.description.is_empty() {
+        output.push_str(&format!("\n\tDescription: {}", tournament.description));
+    }
+
+    output.push_str("\n\tStandings:");
+    let mut members: Vec<_> = tournament.members_list.iter().collect();
+    members.sort_by_key(|member| member.rank);
+    for member in members {
+        let clan = member
+            .clan
+            .as_ref()
+            .map(|clan| format!(", Clan \"{}\"", clan.name))
+            .unwrap_or_default();
+        output.push_str(&format!(
+            "\n\t\t#{} \"{}\" ({}): {} score{clan}",
+            member.rank, member.name, member.tag, member.score
+        ));
+    }
+
+    output
+}
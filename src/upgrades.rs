@@ -0,0 +1,215 @@
+//! Card upgrade costs, by in-game level (see `cards::normalize_level`).
+//! Elite levels (past `MAX_UPGRADE_LEVEL`) aren't covered.
+
+use serde::Serialize;
+
+/// The highest in-game level cards can be upgraded to with cards and gold.
+pub const MAX_UPGRADE_LEVEL: i64 = 14;
+
+/// The gold needed to upgrade any card to each level, starting at level 2.
+const GOLD: [u32; 13] = [
+    5, 20, 50, 150, 400, 1000, 2000, 4000, 8000, 15000, 35000, 75000, 100000,
+];
+
+/// The cards needed to upgrade a card of each rarity to each level, starting
+/// at the level after the rarity's starting level.
+const COMMON_CARDS: [u32; 13] = [2, 4, 10, 20, 50, 100, 200, 400, 800, 1000, 1500, 3000, 5000];
+const RARE_CARDS: [u32; 11] = [2, 4, 10, 20, 50, 100, 200, 400, 500, 750, 1250];
+const EPIC_CARDS: [u32; 8] = [2, 4, 10, 20, 40, 50, 100, 200];
+const LEGENDARY_CARDS: [u32; 5] = [2, 4, 6, 10, 20];
+const CHAMPION_CARDS: [u32; 3] = [2, 8, 20];
+
+/// The rarity of a card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+    Legendary,
+    Champion,
+}
+
+/// The cards and gold needed to upgrade a card.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct UpgradeCost {
+    /// The cards needed.
+    pub cards: u32,
+
+    /// The gold needed.
+    pub gold: u32,
+}
+
+impl Rarity {
+    /// Matches the API's rarity name (e.g. `legendary`) to a rarity.
+    /// Returns an error if it isn't one.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "common" => Self::Common,
+            "rare" => Self::Rare,
+            "epic" => Self::Epic,
+            "legendary" => Self::Legendary,
+            "champion" => Self::Champion,
+            _ => return Err(format!("Unknown card rarity \"{name}\"")),
+        })
+    }
+
+    /// Returns the rarity whose cards start at the given in-game level, or
+    /// `None` if there isn't one.
+    pub fn from_starting_level(level: i64) -> Option<Self> {
+        [
+            Self::Common,
+            Self::Rare,
+            Self::Epic,
+            Self::Legendary,
+            Self::Champion,
+        ]
+        .into_iter()
+        .find(|rarity| rarity.starting_level() == level)
+    }
+
+    /// Returns the in-game level cards of this rarity start at.
+    pub fn starting_level(self) -> i64 {
+        match self {
+            Self::Common => 1,
+            Self::Rare => 3,
+            Self::Epic => 6,
+            Self::Legendary => 9,
+            Self::Champion => 11,
+        }
+    }
+
+    /// Returns the cards needed to upgrade to each level past the starting
+    /// level.
+    fn cards(self) -> &'static [u32] {
+        match self {
+            Self::Common => &COMMON_CARDS,
+            Self::Rare => &RARE_CARDS,
+            Self::Epic => &EPIC_CARDS,
+            Self::Legendary => &LEGENDARY_CARDS,
+            Self::Champion => &CHAMPION_CARDS,
+        }
+    }
+
+    /// Returns the cards and gold needed to upgrade a card of this rarity from
+    /// the `from` level to the `to` level, given the copies already collected.
+    /// Levels past `MAX_UPGRADE_LEVEL` are ignored.
+    /// Returns an error if `from` is below the rarity's starting level.
+    pub fn upgrade_cost(self, from: i64, to: i64, collected: u32) -> Result<UpgradeCost, String> {
+        let to = to.min(MAX_UPGRADE_LEVEL);
+        let start = self.starting_level();
+        if from < start {
+            return Err(format!(
+                "Level {from} is below the starting level of {self:?} cards ({start})"
+            ));
+        }
+
+        let (cards, gold) = ((from + 1)..=to).fold((0, 0), |(cards, gold), level| {
+            let card_index = (level - start - 1) as usize;
+            let gold_index = (level - 2) as usize;
+            (
+                cards + self.cards().get(card_index).copied().unwrap_or(0),
+                gold + GOLD.get(gold_index).copied().unwrap_or(0),
+            )
+        });
+
+        Ok(UpgradeCost {
+            cards: if cards > 0 {
+                cards.saturating_sub(collected)
+            } else {
+                0
+            },
+            gold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The gold needed to upgrade from level 1 to `MAX_UPGRADE_LEVEL`.
+    const TOTAL_GOLD: u32 = 240625;
+
+    #[test]
+    fn upgrades_each_rarity_from_its_start_to_the_max() {
+        let expected = [
+            (Rarity::Common, 12086, TOTAL_GOLD),
+            (Rarity::Rare, 3286, TOTAL_GOLD - 25),
+            (Rarity::Epic, 426, TOTAL_GOLD - 625),
+            (Rarity::Legendary, 42, TOTAL_GOLD - 7625),
+            (Rarity::Champion, 30, 210000),
+        ];
+
+        for (rarity, cards, gold) in expected {
+            let cost = rarity
+                .upgrade_cost(rarity.starting_level(), MAX_UPGRADE_LEVEL, 0)
+                .unwrap();
+            assert_eq!(cost, UpgradeCost { cards, gold }, "{rarity:?}");
+
+            // Levels past the max cost nothing more.
+            let past_max = rarity
+                .upgrade_cost(rarity.starting_level(), MAX_UPGRADE_LEVEL + 2, 0)
+                .unwrap();
+            assert_eq!(past_max, cost, "{rarity:?}");
+        }
+    }
+
+    #[test]
+    fn collected_cards_are_taken_off() {
+        let cost = |collected| Rarity::Legendary.upgrade_cost(9, 11, collected).unwrap();
+
+        assert_eq!(
+            cost(0),
+            UpgradeCost {
+                cards: 6,
+                gold: 23000
+            }
+        );
+        assert_eq!(
+            cost(4),
+            UpgradeCost {
+                cards: 2,
+                gold: 23000
+            }
+        );
+        assert_eq!(
+            cost(50),
+            UpgradeCost {
+                cards: 0,
+                gold: 23000
+            }
+        );
+    }
+
+    #[test]
+    fn downgrades_cost_nothing() {
+        for from in [11, 12, 14] {
+            let cost = Rarity::Epic.upgrade_cost(from, 10, 100).unwrap();
+            assert_eq!(cost, UpgradeCost::default(), "from {from}");
+        }
+    }
+
+    #[test]
+    fn levels_below_the_start_are_an_error() {
+        assert!(Rarity::Rare.upgrade_cost(2, 14, 0).is_err());
+        assert!(Rarity::Champion.upgrade_cost(10, 11, 0).is_err());
+        assert!(Rarity::Common.upgrade_cost(0, 5, 0).is_err());
+    }
+
+    #[test]
+    fn rarities_are_found_by_starting_level() {
+        for rarity in [
+            Rarity::Common,
+            Rarity::Rare,
+            Rarity::Epic,
+            Rarity::Legendary,
+            Rarity::Champion,
+        ] {
+            assert_eq!(
+                Rarity::from_starting_level(rarity.starting_level()),
+                Some(rarity)
+            );
+        }
+        assert_eq!(Rarity::from_starting_level(2), None);
+    }
+}
@@ -1,9 +1,13 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde_json::{to_string_pretty, Value};
-use std::{fs::File, io::Write};
+use std::{env::Args, fs::File, io::Write, str::FromStr};
 
 /// Message for getting bad JSON from the Clash Royale API's response.
 pub const BAD_JSON_ERR_MSG: &str = "Got a bad JSON response from the Clash Royale API";
 
+/// The format of times in the Clash Royale API's responses.
+const API_TIME_FORMAT: &str = "%Y%m%dT%H%M%S%.fZ";
+
 /// Returns `true` if the string is kebab-case, `false` if not.
 pub fn is_kebab_case(s: &str) -> bool {
     // Can't start or end with a hyphen.
@@ -36,7 +40,7 @@ pub fn find_in_json_array<'a, P: FnMut(&Value) -> bool>(
 ) -> Option<&'a Value> {
     json.get(name)
         .and_then(|v| v.as_array())?
-        .into_iter()
+        .iter()
         .find(|&v| predicate(v))
 }
 
@@ -49,3 +53,20 @@ pub fn write_json(json: &Value, path: &str) -> Result<(), String> {
     f.write_all(s.as_bytes())
         .map_err(|_| format!("Failed to write to file `{path}`"))
 }
+
+/// Parses the next argument as a number.
+/// Returns an error mentioning what was expected if it's missing or invalid.
+pub fn next_number<T: FromStr>(args: &mut Args, expected: &str) -> Result<T, String> {
+    let arg = args.next().ok_or_else(|| format!("Expected {expected}"))?;
+
+    arg.parse()
+        .map_err(|_| format!("Expected {expected}, got `{arg}`"))
+}
+
+/// Parses a time from the Clash Royale API (e.g. `20250101T120000.000Z`).
+/// Returns an error if it's in a different format.
+pub fn parse_api_time(s: &str) -> Result<DateTime<Utc>, String> {
+    NaiveDateTime::parse_from_str(s, API_TIME_FORMAT)
+        .map(|time| time.and_utc())
+        .map_err(|_| BAD_JSON_ERR_MSG.to_string())
+}
@@ -0,0 +1,206 @@
+mod config;
+mod events;
+mod sink;
+
+use serde_json::{from_value, to_value};
+use std::{
+    env::Args,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use tracing::warn;
+
+use crate::{
+    api::{api_call, api_get, endpoints, tokens::redact},
+    archive::Archive,
+    clan::war::get_members,
+    models::{battles::Battle, players::PlayerProfile, river_race::CurrentRiverRace},
+    util::BAD_JSON_ERR_MSG,
+};
+
+pub use config::WatchConfig;
+use events::{get_player_events, get_war_events, WatchEvent};
+
+/// The config file read if no other is given.
+const DEFAULT_CONFIG_PATH: &str = "watch.toml";
+
+/// The reason the API gives for rejecting requests over the rate limit.
+const THROTTLED_REASON: &str = "requestThrottled";
+
+/// The number of times a throttled request is retried.
+const MAX_RETRIES: u32 = 3;
+
+/// Spaces out API requests and retries throttled ones, waiting longer each
+/// time.
+struct RateLimiter {
+    /// The least time between requests.
+    delay: Duration,
+
+    /// When the last request was made.
+    last: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Makes the request once enough time has passed since the last one.
+    fn call<T>(&mut self, request: impl Fn() -> Result<T, String>) -> Result<T, String> {
+        let mut backoff = self.delay.max(Duration::from_secs(1));
+        let mut retries = 0;
+
+        loop {
+            if let Some(last) = self.last {
+                sleep(self.delay.saturating_sub(last.elapsed()));
+            }
+            self.last = Some(Instant::now());
+
+            match request() {
+                Err(e) if e.contains(THROTTLED_REASON) && retries < MAX_RETRIES => {
+                    warn!(
+                        retry = retries + 1,
+                        backoff_ms = backoff.as_millis() as u64,
+                        "Throttled, retrying"
+                    );
+                    sleep(backoff);
+                    backoff *= 2;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Polls the configured players and clans, storing snapshots and sending
+/// events for what changed.
+struct Watcher {
+    config: WatchConfig,
+    archive: Archive,
+    limiter: RateLimiter,
+}
+
+impl Watcher {
+    /// Polls everything once.
+    /// Errors are printed and don't stop the other players and clans being
+    /// polled.
+    fn poll(&mut self) {
+        for tag in self.config.players.clone() {
+            if let Err(e) = self.poll_player(&tag) {
+                eprintln!(
+                    "Failed to poll player #{}: {}",
+                    tag.trim_start_matches('#'),
+                    redact(&e)
+                );
+            }
+        }
+        for tag in self.config.clans.clone() {
+            if let Err(e) = self.poll_clan(&tag) {
+                eprintln!(
+                    "Failed to poll clan #{}: {}",
+                    tag.trim_start_matches('#'),
+                    redact(&e)
+                );
+            }
+        }
+    }
+
+    /// Stores the player's profile and battles, sending events for what
+    /// changed since the last snapshot.
+    fn poll_player(&mut self, tag: &str) -> Result<(), String> {
+        let json = self.limiter.call(|| api_call(&endpoints::player(tag)))?;
+        let new: PlayerProfile = from_value(json.clone()).map_err(|_| BAD_JSON_ERR_MSG)?;
+
+        if let Some(old) = self.archive.latest_snapshot("player", tag)? {
+            let old: PlayerProfile = from_value(old).map_err(|_| BAD_JSON_ERR_MSG)?;
+            self.emit_all(get_player_events(&old, &new, self.config.trophy_step));
+        }
+        self.archive.add_snapshot("player", tag, &json)?;
+
+        if self.config.battles {
+            let battles: Vec<Battle> =
+                self.limiter.call(|| api_get(&endpoints::battle_log(tag)))?;
+            self.archive.upsert(&battles)?;
+        }
+
+        Ok(())
+    }
+
+    /// Stores the clan's members and river race, sending events for what
+    /// changed since the last snapshot.
+    fn poll_clan(&mut self, tag: &str) -> Result<(), String> {
+        let members = self.limiter.call(|| get_members(tag))?;
+        let json = self
+            .limiter
+            .call(|| api_call(&endpoints::current_river_race(tag)))?;
+        let new: CurrentRiverRace = from_value(json.clone()).map_err(|_| BAD_JSON_ERR_MSG)?;
+
+        if let Some(old) = self.archive.latest_snapshot("river_race", tag)? {
+            let old: CurrentRiverRace = from_value(old).map_err(|_| BAD_JSON_ERR_MSG)?;
+            self.emit_all(get_war_events(&old, &new, &members));
+        }
+        self.archive.add_snapshot("river_race", tag, &json)?;
+
+        let members = to_value(&members).map_err(|_| BAD_JSON_ERR_MSG)?;
+        self.archive.add_snapshot("clan_members", tag, &members)?;
+
+        Ok(())
+    }
+
+    /// Sends the events to every sink.
+    /// Errors are printed and don't stop the event being sent to the other
+    /// sinks.
+    fn emit_all(&self, events: Vec<WatchEvent>) {
+        for event in &events {
+            for sink in &self.config.sinks {
+                if let Err(e) = sink.emit(event) {
+                    eprintln!("Failed to send event: {}", redact(&e));
+                }
+            }
+        }
+    }
+}
+
+/// Parses the input arguments after `watch` and polls the configured players
+/// and clans until stopped, or returns an error if any are encountered.
+pub fn process_watch_commands(mut args: Args) -> Result<(), String> {
+    let mut path = DEFAULT_CONFIG_PATH.to_string();
+    let mut once = false;
+    let mut test = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            // The config file.
+            "--config" => path = args.next().ok_or("Expected config file")?,
+
+            // Poll once and exit, e.g. when run by cron.
+            "--once" => once = true,
+
+            // Send a sample event to every sink and exit.
+            "--test" => test = true,
+
+            // Errors:
+            _ => return Err(format!("Unexpected input: `{arg}`")),
+        }
+    }
+
+    let config = WatchConfig::load(&path)?;
+    if test {
+        let event = WatchEvent::sample();
+        return config.sinks.iter().try_for_each(|sink| sink.emit(&event));
+    }
+
+    let mut watcher = Watcher {
+        archive: Archive::open(&config.archive)?,
+        limiter: RateLimiter {
+            delay: Duration::from_millis(config.request_delay),
+            last: None,
+        },
+        config,
+    };
+
+    loop {
+        watcher.poll();
+        if once {
+            return Ok(());
+        }
+        sleep(Duration::from_secs(watcher.config.interval));
+    }
+}
@@ -0,0 +1,93 @@
+use serde::Deserialize;
+use std::fs::read_to_string;
+
+use crate::{archive::DEFAULT_ARCHIVE_PATH, config};
+
+use super::sink::Sink;
+
+/// What `watch` polls and how often, from a TOML file, e.g.:
+/// ```toml
+/// interval = 600
+/// players = ["QQUUCL"]
+/// clans = ["9YJUPU9"]
+///
+/// [[sinks]]
+/// kind = "file"
+/// path = "events.jsonl"
+///
+/// [[sinks]]
+/// kind = "webhook"
+/// url = "https://discord.com/api/webhooks/..."
+/// format = "discord"
+/// templates = { level-up = "{name} reached king level {level}!" }
+/// ```
+/// Everything but the players or clans is optional.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchConfig {
+    /// The seconds between polls.
+    pub interval: u64,
+
+    /// The fewest milliseconds between API requests.
+    pub request_delay: u64,
+
+    /// The archive's database file, where snapshots and battles are stored.
+    pub archive: String,
+
+    /// The tags of the players to watch, which can include `@NAME` rosters
+    /// from the config profile.
+    pub players: Vec<String>,
+
+    /// The tags of the clans to watch, which can include rosters.
+    pub clans: Vec<String>,
+
+    /// Whether to archive the players' battle logs.
+    pub battles: bool,
+
+    /// The trophy count multiples that are milestones (e.g. every 1000).
+    pub trophy_step: u32,
+
+    /// Where events are sent.
+    pub sinks: Vec<Sink>,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            interval: 600,
+            request_delay: 250,
+            archive: DEFAULT_ARCHIVE_PATH.to_string(),
+            players: Vec::new(),
+            clans: Vec::new(),
+            battles: true,
+            trophy_step: 1000,
+            sinks: vec![Sink::default()],
+        }
+    }
+}
+
+impl WatchConfig {
+    /// Reads the config from the TOML file at the path.
+    /// Returns an error if it can't be read or is invalid.
+    pub fn load(path: &str) -> Result<Self, String> {
+        let s =
+            read_to_string(path).map_err(|_| format!("Failed to read watch config `{path}`"))?;
+        let mut config: Self = toml::from_str(&s).map_err(|e| {
+            format!(
+                "Invalid watch config `{path}` {}",
+                config::describe_toml_error(&s, &e)
+            )
+        })?;
+        config.players = config::profile().expand_tags(&config.players)?;
+        config.clans = config::profile().expand_tags(&config.clans)?;
+
+        if config.players.is_empty() && config.clans.is_empty() {
+            return Err(format!("No players or clans to watch in `{path}`"));
+        }
+        if config.trophy_step == 0 {
+            return Err("The trophy step must be above 0".to_string());
+        }
+
+        Ok(config)
+    }
+}
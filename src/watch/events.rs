@@ -0,0 +1,140 @@
+use serde::Serialize;
+
+use crate::{
+    clan::war::DECKS_PER_DAY,
+    models::{clans::ClanMember, players::PlayerProfile, river_race::CurrentRiverRace},
+};
+
+/// A change `watch` noticed between polls.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum WatchEvent {
+    /// A player's king level went up.
+    LevelUp {
+        tag: String,
+        name: String,
+        level: u32,
+    },
+
+    /// A player unlocked a card.
+    NewCard {
+        tag: String,
+        name: String,
+        card: String,
+    },
+
+    /// A player's trophies passed a milestone.
+    TrophyMilestone {
+        tag: String,
+        name: String,
+        milestone: u32,
+        trophies: u32,
+    },
+
+    /// A clan member ended a war day with decks unused.
+    WarDeckUnused {
+        clan: String,
+        tag: String,
+        name: String,
+        unused: u32,
+    },
+}
+
+impl WatchEvent {
+    /// Returns an event for testing sinks.
+    pub fn sample() -> Self {
+        Self::LevelUp {
+            tag: "#QQUUCL".to_string(),
+            name: "parse-royale".to_string(),
+            level: 14,
+        }
+    }
+
+    /// Returns a one-line description of the event.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::LevelUp { tag, name, level } => {
+                format!("\"{name}\" ({tag}) reached king level {level}")
+            }
+            Self::NewCard { tag, name, card } => format!("\"{name}\" ({tag}) unlocked {card}"),
+            Self::TrophyMilestone {
+                tag,
+                name,
+                milestone,
+                trophies,
+            } => format!("\"{name}\" ({tag}) passed {milestone} trophies ({trophies})"),
+            Self::WarDeckUnused {
+                clan,
+                tag,
+                name,
+                unused,
+            } => format!("\"{name}\" ({tag}) left {unused} war decks unused in {clan}"),
+        }
+    }
+}
+
+/// Returns the events between the player's old and new profiles.
+/// Trophy milestones are the multiples of `trophy_step` passed going up.
+pub fn get_player_events(
+    old: &PlayerProfile,
+    new: &PlayerProfile,
+    trophy_step: u32,
+) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+
+    if new.exp_level > old.exp_level {
+        events.push(WatchEvent::LevelUp {
+            tag: new.tag.clone(),
+            name: new.name.clone(),
+            level: new.exp_level,
+        });
+    }
+
+    for card in &new.cards {
+        if !old.cards.iter().any(|owned| owned.id == card.id) {
+            events.push(WatchEvent::NewCard {
+                tag: new.tag.clone(),
+                name: new.name.clone(),
+                card: card.name.clone(),
+            });
+        }
+    }
+
+    if new.trophies / trophy_step > old.trophies / trophy_step {
+        events.push(WatchEvent::TrophyMilestone {
+            tag: new.tag.clone(),
+            name: new.name.clone(),
+            milestone: new.trophies / trophy_step * trophy_step,
+            trophies: new.trophies,
+        });
+    }
+
+    events
+}
+
+/// Returns the events between the clan's old and new river race.
+/// When a war day has ended, every current member with decks unused as of the
+/// old race is reported.
+pub fn get_war_events(
+    old: &CurrentRiverRace,
+    new: &CurrentRiverRace,
+    members: &[ClanMember],
+) -> Vec<WatchEvent> {
+    let same_day = (old.section_index, old.period_index) == (new.section_index, new.period_index);
+    if same_day || old.period_type == "training" {
+        return Vec::new();
+    }
+
+    old.clan
+        .participants
+        .iter()
+        .filter(|participant| participant.decks_used_today < DECKS_PER_DAY)
+        .filter(|participant| members.iter().any(|member| member.tag == participant.tag))
+        .map(|participant| WatchEvent::WarDeckUnused {
+            clan: old.clan.name.clone(),
+            tag: participant.tag.clone(),
+            name: participant.name.clone(),
+            unused: DECKS_PER_DAY - participant.decks_used_today,
+        })
+        .collect()
+}
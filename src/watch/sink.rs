@@ -0,0 +1,250 @@
+use chrono::Utc;
+use reqwest::{blocking::Client, Url};
+use serde::Deserialize;
+use serde_json::{json, to_string, to_value, Map, Value};
+use std::{collections::HashMap, fs::OpenOptions, io::Write};
+
+use crate::util::BAD_JSON_ERR_MSG;
+
+use super::events::WatchEvent;
+
+/// Where `watch` sends its events.
+#[derive(Default, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Sink {
+    /// Printed to stdout, one per line.
+    #[default]
+    Stdout,
+
+    /// Appended to a file as JSON lines.
+    File { path: String },
+
+    /// POSTed to a webhook URL, with the event's description added to its
+    /// fields.
+    Webhook {
+        url: String,
+
+        /// The shape of the request body.
+        #[serde(default)]
+        format: WebhookFormat,
+
+        /// Message templates by event name (e.g. `level-up`), for Discord and
+        /// Slack. Events without one use their description.
+        #[serde(default)]
+        templates: HashMap<String, String>,
+    },
+}
+
+/// The shape of a webhook's request body.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// The event's fields, as written to files.
+    #[default]
+    Json,
+
+    /// A Discord webhook message (`{"content": ...}`).
+    Discord,
+
+    /// A Slack incoming webhook message (`{"text": ...}`).
+    Slack,
+}
+
+impl Sink {
+    /// Sends the event, or returns an error if it can't be.
+    pub fn emit(&self, event: &WatchEvent) -> Result<(), String> {
+        let time = Utc::now();
+        let mut fields = match to_value(event).map_err(|_| BAD_JSON_ERR_MSG)? {
+            Value::Object(fields) => fields,
+            _ => return Err(BAD_JSON_ERR_MSG.to_string()),
+        };
+        fields.insert("time".to_string(), time.to_rfc3339().into());
+
+        match self {
+            Self::Stdout => {
+                println!(
+                    "- {}: {}",
+                    time.format("%Y-%m-%d %H:%M UTC"),
+                    event.describe()
+                );
+                Ok(())
+            }
+            Self::File { path } => {
+                let line = to_string(&fields).map_err(|_| BAD_JSON_ERR_MSG)?;
+
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut f| writeln!(f, "{line}"))
+                    .map_err(|_| format!("Failed to write to file `{path}`"))
+            }
+            Self::Webhook {
+                url,
+                format,
+                templates,
+            } => {
+                fields.insert("description".to_string(), event.describe().into());
+                let message = || match fields.get("event").and_then(|v| v.as_str()) {
+                    Some(name) if templates.contains_key(name) => {
+                        render_template(&templates[name], &fields)
+                    }
+                    _ => event.describe(),
+                };
+                let body = match format {
+                    WebhookFormat::Json => Value::Object(fields.clone()),
+                    WebhookFormat::Discord => json!({ "content": message() }),
+                    WebhookFormat::Slack => json!({ "text": message() }),
+                };
+
+                post_json(url, &body)
+            }
+        }
+    }
+}
+
+/// Returns the template with each `{field}` replaced by the event's field of
+/// that name. Unknown fields are left as they are.
+/// The template is read once, so braces in field values (e.g. names) are never
+/// replaced.
+fn render_template(template: &str, fields: &Map<String, Value>) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let field = after
+            .find('}')
+            .and_then(|end| Some((end, fields.get(&after[..end])?)));
+
+        match field {
+            Some((end, Value::String(value))) => {
+                output.push_str(value);
+                rest = &after[end + 1..];
+            }
+            Some((end, value)) => {
+                output.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+/// POSTs the JSON to the URL, or returns an error if it fails or isn't
+/// accepted.
+/// Errors only name the URL's host, since webhook URLs hold their secret.
+fn post_json(url: &str, body: &Value) -> Result<(), String> {
+    let host = Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(String::from))
+        .unwrap_or_else(|| "an invalid URL".to_string());
+    let response = Client::new()
+        .post(url)
+        .json(body)
+        .send()
+        .map_err(|_| format!("Got no response from webhook at `{host}`"))?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Webhook at `{host}` responded with {}",
+            response.status()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::from_str;
+    use std::thread::{spawn, JoinHandle};
+    use tiny_http::{Response, Server};
+
+    use super::*;
+
+    /// Starts a local stand-in for a webhook that answers one request with
+    /// the status, and returns its URL and a handle giving the request's body.
+    fn stand_in(status: u16) -> (String, JoinHandle<String>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = spawn(move || {
+            let mut request = server.recv().unwrap();
+            let mut body = String::new();
+            request.as_reader().read_to_string(&mut body).unwrap();
+            request.respond(Response::empty(status)).unwrap();
+            body
+        });
+
+        (
+            format!("http://127.0.0.1:{port}/hooks/secret-token"),
+            handle,
+        )
+    }
+
+    /// Returns a webhook sink for the URL.
+    fn webhook(url: String, format: WebhookFormat) -> Sink {
+        Sink::Webhook {
+            url,
+            format,
+            templates: HashMap::from([(
+                "level-up".to_string(),
+                "{name} is level {level}".to_string(),
+            )]),
+        }
+    }
+
+    #[test]
+    fn posts_events_to_webhooks() {
+        let (url, handle) = stand_in(204);
+        webhook(url, WebhookFormat::Json)
+            .emit(&WatchEvent::sample())
+            .unwrap();
+
+        let body: Value = from_str(&handle.join().unwrap()).unwrap();
+        assert_eq!(body["event"], "level-up");
+        assert_eq!(body["level"], 14);
+        assert_eq!(body["description"], WatchEvent::sample().describe());
+        assert!(body["time"].is_string());
+
+        let (url, handle) = stand_in(200);
+        webhook(url, WebhookFormat::Discord)
+            .emit(&WatchEvent::sample())
+            .unwrap();
+
+        let body: Value = from_str(&handle.join().unwrap()).unwrap();
+        assert_eq!(body, json!({ "content": "parse-royale is level 14" }));
+    }
+
+    #[test]
+    fn rejected_webhook_is_an_error_without_its_url() {
+        let (url, handle) = stand_in(500);
+        let error = webhook(url, WebhookFormat::Slack)
+            .emit(&WatchEvent::sample())
+            .unwrap_err();
+        handle.join().unwrap();
+
+        assert_eq!(
+            error,
+            "Webhook at `127.0.0.1` responded with 500 Internal Server Error"
+        );
+    }
+
+    #[test]
+    fn templates_are_rendered_in_one_pass() {
+        let fields = json!({ "name": "{level}", "level": 14 });
+        let fields = fields.as_object().unwrap();
+
+        assert_eq!(
+            render_template("{name} at {level} {unknown} {", fields),
+            "{level} at 14 {unknown} {"
+        );
+    }
+}